@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+// Known good answers for one set of inputs, one per line:
+//
+//     # day part answer
+//     1 1 1292
+//     13 2 #..#\n#..#
+//
+// Answers that span lines (day 13) have their newlines written as `\n`.
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers(BTreeMap<(usize, usize), String>);
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Line(usize, String),
+    Io(String, std::io::Error),
+}
+
+impl std::error::Error for AnswersError {}
+
+impl std::fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            AnswersError::Line(n, line) => {
+                write!(
+                    f,
+                    "line {}: expected \"<day> <part> <answer>\", got \"{}\"",
+                    n, line
+                )
+            }
+            AnswersError::Io(path, e) => write!(f, "couldn't use {}: {}", path, e),
+        }
+    }
+}
+
+fn escape(answer: &str) -> String {
+    answer.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(answer: &str) -> String {
+    let mut out = String::new();
+    let mut chars = answer.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                out.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                out.push('\\');
+                chars.next();
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Answers, AnswersError> {
+        let mut answers = Answers::default();
+
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut parts = line.splitn(3, ' ');
+            let day = parts.next().and_then(|d| d.parse::<usize>().ok());
+            let part = parts.next().and_then(|p| p.parse::<usize>().ok());
+            match (day, part, parts.next()) {
+                (Some(day), Some(part @ 1..=2), Some(answer)) => {
+                    answers.0.insert((day, part), unescape(answer));
+                }
+                _ => return Err(AnswersError::Line(n + 1, line.to_string())),
+            }
+        }
+
+        Ok(answers)
+    }
+
+    // A missing file is just an empty one; everything comes back MISSING.
+    pub fn load(path: &Path) -> Result<Answers, AnswersError> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Answers::parse(&text),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(AnswersError::Io(path.display().to_string(), e)),
+        }
+    }
+
+    pub fn get(&self, day: usize, part: usize) -> Option<&str> {
+        self.0.get(&(day, part)).map(|a| a.as_str())
+    }
+
+    // Add new answers to the end of the file, leaving anything already there alone.
+    pub fn append(path: &Path, new: &[(usize, usize, String)]) -> Result<(), AnswersError> {
+        let io_err = |e| AnswersError::Io(path.display().to_string(), e);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(io_err)?;
+
+        for (day, part, answer) in new {
+            writeln!(file, "{} {} {}", day, part, escape(answer)).map_err(io_err)?;
+        }
+        Ok(())
+    }
+}
+
+// A line by line comparison, which is plenty for answers.  Lines that match
+// are indented, the rest are shown as - expected / + got.
+pub fn diff(expected: &str, got: &str) -> Vec<String> {
+    let expected: Vec<&str> = expected.lines().collect();
+    let got: Vec<&str> = got.lines().collect();
+    let mut out = vec![];
+
+    for n in 0..expected.len().max(got.len()) {
+        match (expected.get(n), got.get(n)) {
+            (Some(e), Some(g)) if e == g => out.push(format!("  {}", e)),
+            (e, g) => {
+                if let Some(e) = e {
+                    out.push(format!("- {}", e));
+                }
+                if let Some(g) = g {
+                    out.push(format!("+ {}", g));
+                }
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        let answers = Answers::parse("# day part answer\n1 1 7\n\n13 2 #.\\n.#\n1 2 a b").unwrap();
+
+        assert!(answers.get(1, 1) == Some("7"));
+        assert!(answers.get(1, 2) == Some("a b"));
+        assert!(answers.get(13, 2) == Some("#.\n.#"));
+        assert!(answers.get(2, 1).is_none());
+        assert!(matches!(
+            Answers::parse("1 1 7\n1 3 7"),
+            Err(AnswersError::Line(2, _))
+        ));
+        assert!(Answers::parse("1 1").is_err());
+    }
+
+    #[test]
+    fn escape_test() {
+        for answer in ["plain", "#.\n.#", "back\\slash", "\\n"] {
+            assert!(unescape(&escape(answer)) == answer);
+        }
+    }
+
+    #[test]
+    fn diff_test() {
+        assert!(diff("#.\n.#", "#.\n##") == vec!["  #.", "- .#", "+ ##"]);
+        assert!(diff("900", "901") == vec!["- 900", "+ 901"]);
+        assert!(diff("a", "a\nb") == vec!["  a", "+ b"]);
+    }
+}
@@ -0,0 +1,893 @@
+mod answers;
+mod pool;
+mod show;
+
+use answers::Answers;
+use common::bench::{Report, Stats};
+use common::example::Checked;
+use common::progress::{self, Budget};
+use common::report::DayReport;
+use common::{json, table, Rng, Simulation, Solution, Source, Visualise};
+use pool::Outcome;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+type Error = Box<dyn std::error::Error>;
+type Simulate = fn(Option<Source>) -> Result<Box<dyn Simulation>, Error>;
+
+// Everything the runner can do with a day, monomorphised for each one.
+struct Day {
+    run: fn(Option<Source>) -> Result<Vec<String>, Error>,
+    report: fn(Option<Source>) -> Result<DayReport, Error>,
+    bench: fn(Option<Source>, usize) -> Result<Report, Error>,
+    examples: fn() -> Vec<Checked>,
+    generate: fn(&mut Rng, Option<usize>) -> Vec<String>,
+    // for the days with something to watch
+    simulation: Option<Simulate>,
+}
+
+fn report<S: Solution>(source: Option<Source>) -> Result<DayReport, Error> {
+    common::report::report::<S>(common::load::<S>(source)?)
+}
+
+fn bench<S: Solution>(source: Option<Source>, runs: usize) -> Result<Report, Error> {
+    let lines = common::load::<S>(source)?;
+
+    common::bench::bench::<S>(&lines, runs)
+}
+
+fn simulation<S: Visualise>(source: Option<Source>) -> Result<Box<dyn Simulation>, Error> {
+    let input = S::parse(common::load::<S>(source)?)?;
+
+    Ok(S::simulation(&input))
+}
+
+macro_rules! day {
+    ($solution:ty) => {
+        Day {
+            run: common::run::<$solution>,
+            report: report::<$solution>,
+            bench: bench::<$solution>,
+            examples: common::example::check::<$solution>,
+            generate: <$solution>::generate,
+            simulation: None,
+        }
+    };
+    ($solution:ty, visualise) => {
+        Day {
+            simulation: Some(simulation::<$solution>),
+            ..day!($solution)
+        }
+    };
+}
+
+// Index 0 is day 1, and so on.
+const DAYS: [Day; 25] = [
+    day!(day1::Day1),
+    day!(day2::Day2),
+    day!(day3::Day3),
+    day!(day4::Day4),
+    day!(day5::Day5),
+    day!(day6::Day6),
+    day!(day7::Day7),
+    day!(day8::Day8),
+    day!(day9::Day9, visualise),
+    day!(day10::Day10),
+    day!(day11::Day11, visualise),
+    day!(day12::Day12),
+    day!(day13::Day13, visualise),
+    day!(day14::Day14),
+    day!(day15::Day15),
+    day!(day16::Day16),
+    day!(day17::Day17),
+    day!(day18::Day18),
+    day!(day19::Day19),
+    day!(day20::Day20, visualise),
+    day!(day21::Day21),
+    day!(day22::Day22),
+    day!(day23::Day23),
+    day!(day24::Day24),
+    day!(day25::Day25, visualise),
+];
+
+const USAGE: &str = "usage: aoc run [--json] [--parallel] [--jobs <n>] [--timeout <secs>]
+               [--budget <secs>] [--max-steps <n>] [--progress]
+               [--input <path>] [--input-dir <dir>] <days>...
+       aoc bench [--runs <n>] [--json] [--input <path>] [--input-dir <dir>] <days>...
+       aoc verify [--answers <file>] [--save] [--input <path>] [--input-dir <dir>] <days>...
+       aoc example <days>...
+       aoc generate [--seed <n>] [--size <n>] [--input-dir <dir>] <days>...
+       aoc show [--delay <secs>] [--input <path>] [--input-dir <dir>] <day>
+       aoc export --output <path> [--scale <n>] [--steps <n>] [--delay <secs>]
+                  [--input <path>] [--input-dir <dir>] <day>
+
+days can be a single day (7), an inclusive range (1..25) or \"all\"
+
+--input      read a single day's input from <path>, or stdin if it's -
+--input-dir  read each day's input from <dir>/dayN, overriding $AOC_INPUT_DIR
+--runs       how many times to parse and solve each day (default 10)
+--json       print answers, diagnostics and timings as JSON, times in nanoseconds
+--parallel   run the days at the same time, one per thread
+--jobs       how many threads to use with --parallel (default one per CPU)
+--timeout    with --parallel, give up on a day after this many seconds
+--budget     stop any long search (days 19, 23 and 24) after this many seconds
+--max-steps  stop any long search after this many steps, however the day counts them
+--progress   say how long searches are getting on, on stderr
+--answers    expected answers to check against, by default `answers` in the
+             input directory, or at the top of the checkout
+--save       add any answers that were MISSING to the answers file
+--seed       generate the same input as a previous run (default random)
+--size       how big an input to generate, in whatever way the day counts it
+             (default about the size of the puzzle's)
+--delay      how long to show each step for while playing (default 0.2)
+--output     a .gif to save the animation to, or a directory for a PNG per step
+--scale      how many pixels wide each cell is in the pictures (default 4)
+--steps      stop after this many steps, for simulations that go on a while
+             (default 1000)";
+
+const DEFAULT_RUNS: usize = 10;
+const DEFAULT_DELAY: Duration = Duration::from_millis(200);
+const DEFAULT_SCALE: usize = 4;
+const DEFAULT_STEPS: usize = 1000;
+
+#[derive(Debug)]
+enum UsageError {
+    Command,
+    Days,
+    Day(String),
+    // a range of days that goes backwards
+    Range(String),
+    Flag(String),
+    Value(String),
+    Runs(String),
+    Jobs(String),
+    // the flag, and what it was given
+    Seconds(String, String),
+    Steps(String),
+    Seed(String),
+    Size(String),
+    Input,
+    Output,
+    Show(usize),
+    Scale(String),
+    NoOutput,
+    OneDay,
+    Interactive,
+}
+
+impl std::error::Error for UsageError {}
+
+// How many answers were wrong, or didn't come out at all.
+#[derive(Debug)]
+struct VerifyError(usize);
+
+impl std::error::Error for VerifyError {}
+
+impl std::fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} answer(s) failed verification", self.0)
+    }
+}
+
+impl std::fmt::Display for UsageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            UsageError::Command => write!(f, "expected a command\n\n{}", USAGE),
+            UsageError::Days => write!(f, "expected at least one day\n\n{}", USAGE),
+            UsageError::Day(day) => write!(f, "\"{}\" isn't a day between 1 and 25", day),
+            UsageError::Range(range) => {
+                write!(f, "\"{}\" goes backwards, try the lower day first", range)
+            }
+            UsageError::Flag(flag) => write!(f, "unexpected flag {}\n\n{}", flag, USAGE),
+            UsageError::Value(flag) => write!(f, "{} needs a value\n\n{}", flag, USAGE),
+            UsageError::Runs(runs) => write!(f, "\"{}\" isn't a positive number of runs", runs),
+            UsageError::Jobs(jobs) => write!(f, "\"{}\" isn't a positive number of threads", jobs),
+            UsageError::Seconds(flag, secs) => {
+                write!(
+                    f,
+                    "{} \"{}\" isn't a positive number of seconds",
+                    flag, secs
+                )
+            }
+            UsageError::Steps(steps) => write!(f, "\"{}\" isn't a positive number of steps", steps),
+            UsageError::Seed(seed) => write!(f, "\"{}\" isn't a seed", seed),
+            UsageError::Size(size) => write!(f, "\"{}\" isn't a positive size", size),
+            UsageError::Input => write!(f, "--input only makes sense for a single day"),
+            UsageError::Output => write!(f, "more than one day needs --input-dir to write to"),
+            UsageError::Show(day) => {
+                let days: Vec<String> = (1..=DAYS.len())
+                    .filter(|&day| DAYS[day - 1].simulation.is_some())
+                    .map(|day| day.to_string())
+                    .collect();
+                write!(
+                    f,
+                    "day {} has nothing to show, only days {} do",
+                    day,
+                    days.join(", ")
+                )
+            }
+            UsageError::Scale(scale) => write!(f, "\"{}\" isn't a positive scale", scale),
+            UsageError::NoOutput => write!(f, "export needs an --output to write to"),
+            UsageError::OneDay => write!(f, "show can only show one day at a time"),
+            UsageError::Interactive => {
+                write!(
+                    f,
+                    "show reads commands from stdin, so it can't read input from there"
+                )
+            }
+        }
+    }
+}
+
+// Every day we're going to run, in the order they were asked for.
+struct Selection(Vec<usize>);
+
+impl FromStr for Selection {
+    type Err = UsageError;
+
+    fn from_str(buf: &str) -> Result<Self, Self::Err> {
+        let parse_day = |day: &str| match day.parse::<usize>() {
+            Ok(n) if (1..=DAYS.len()).contains(&n) => Ok(n),
+            _ => Err(UsageError::Day(day.to_string())),
+        };
+
+        if buf == "all" {
+            Ok(Selection((1..=DAYS.len()).collect()))
+        } else if let Some((start, end)) = buf.split_once("..") {
+            // Everyone says "days 1 to 25" and means 25 too, so ranges are inclusive.
+            let start = parse_day(start)?;
+            let end = parse_day(end.trim_start_matches('='))?;
+            if start > end {
+                return Err(UsageError::Range(buf.to_string()));
+            }
+            Ok(Selection((start..=end).collect()))
+        } else {
+            Ok(Selection(vec![parse_day(buf)?]))
+        }
+    }
+}
+
+// Everything after the command.  Not every command looks at every option.
+struct Options {
+    days: Vec<usize>,
+    input: Option<Source>,
+    input_dir: Option<PathBuf>,
+    runs: usize,
+    json: bool,
+    answers: Option<PathBuf>,
+    save: bool,
+    parallel: bool,
+    jobs: Option<usize>,
+    timeout: Option<Duration>,
+    budget: Budget,
+    progress: bool,
+    seed: Option<u64>,
+    size: Option<usize>,
+    delay: Duration,
+    output: Option<PathBuf>,
+    scale: usize,
+    steps: usize,
+}
+
+impl Options {
+    fn parse(args: &[String], flags: &[&str]) -> Result<Options, UsageError> {
+        let mut options = Options {
+            days: vec![],
+            input: None,
+            input_dir: None,
+            runs: DEFAULT_RUNS,
+            json: false,
+            answers: None,
+            save: false,
+            parallel: false,
+            jobs: None,
+            timeout: None,
+            budget: Budget::default(),
+            progress: false,
+            seed: None,
+            size: None,
+            delay: DEFAULT_DELAY,
+            output: None,
+            scale: DEFAULT_SCALE,
+            steps: DEFAULT_STEPS,
+        };
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if !arg.starts_with("--") {
+                options.days.extend(arg.parse::<Selection>()?.0);
+                continue;
+            }
+            if !flags.contains(&arg.as_str()) {
+                return Err(UsageError::Flag(arg.clone()));
+            }
+
+            let mut value = || args.next().ok_or(UsageError::Value(arg.clone()));
+            match arg.as_str() {
+                "--input" => options.input = Some(Source::from(value()?.as_str())),
+                "--input-dir" => options.input_dir = Some(PathBuf::from(value()?)),
+                "--runs" => {
+                    let runs = value()?;
+                    options.runs = match runs.parse::<usize>() {
+                        Ok(n) if n > 0 => n,
+                        _ => return Err(UsageError::Runs(runs.clone())),
+                    }
+                }
+                "--json" => options.json = true,
+                "--answers" => options.answers = Some(PathBuf::from(value()?)),
+                "--save" => options.save = true,
+                "--parallel" => options.parallel = true,
+                // Neither of these means anything without --parallel, so they imply it.
+                "--jobs" => {
+                    let jobs = value()?;
+                    options.jobs = match jobs.parse::<usize>() {
+                        Ok(n) if n > 0 => Some(n),
+                        _ => return Err(UsageError::Jobs(jobs.clone())),
+                    };
+                    options.parallel = true;
+                }
+                "--timeout" => {
+                    let secs = value()?;
+                    options.timeout = match secs.parse::<f64>() {
+                        Ok(s) if s > 0.0 && s.is_finite() => Some(Duration::from_secs_f64(s)),
+                        _ => return Err(UsageError::Seconds(arg.clone(), secs.clone())),
+                    };
+                    options.parallel = true;
+                }
+                "--budget" => {
+                    let secs = value()?;
+                    options.budget.time = match secs.parse::<f64>() {
+                        Ok(s) if s > 0.0 && s.is_finite() => Some(Duration::from_secs_f64(s)),
+                        _ => return Err(UsageError::Seconds(arg.clone(), secs.clone())),
+                    };
+                }
+                "--max-steps" => {
+                    let steps = value()?;
+                    options.budget.steps = match steps.parse::<u64>() {
+                        Ok(n) if n > 0 => Some(n),
+                        _ => return Err(UsageError::Steps(steps.clone())),
+                    };
+                }
+                "--progress" => options.progress = true,
+                "--seed" => {
+                    let seed = value()?;
+                    options.seed = Some(
+                        seed.parse::<u64>()
+                            .map_err(|_| UsageError::Seed(seed.clone()))?,
+                    );
+                }
+                "--delay" => {
+                    let secs = value()?;
+                    options.delay = match secs.parse::<f64>() {
+                        Ok(s) if s > 0.0 && s.is_finite() => Duration::from_secs_f64(s),
+                        _ => return Err(UsageError::Seconds(arg.clone(), secs.clone())),
+                    };
+                }
+                "--output" => options.output = Some(PathBuf::from(value()?)),
+                "--scale" => {
+                    let scale = value()?;
+                    options.scale = match scale.parse::<usize>() {
+                        Ok(n) if n > 0 => n,
+                        _ => return Err(UsageError::Scale(scale.clone())),
+                    };
+                }
+                "--steps" => {
+                    let steps = value()?;
+                    options.steps = steps
+                        .parse::<usize>()
+                        .map_err(|_| UsageError::Steps(steps.clone()))?;
+                }
+                "--size" => {
+                    let size = value()?;
+                    options.size = match size.parse::<usize>() {
+                        Ok(n) if n > 0 => Some(n),
+                        _ => return Err(UsageError::Size(size.clone())),
+                    };
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        if options.days.is_empty() {
+            return Err(UsageError::Days);
+        }
+        if options.input.is_some() && options.days.len() > 1 {
+            return Err(UsageError::Input);
+        }
+
+        Ok(options)
+    }
+
+    // Each day paired with where to read its input from, if not up to the day itself.
+    fn sources(&mut self) -> Vec<(usize, Option<Source>)> {
+        let mut input = self.input.take();
+
+        self.days
+            .iter()
+            .map(|&day| match (&self.input_dir, input.take()) {
+                (_, Some(source)) => (day, Some(source)),
+                (Some(dir), None) => (day, Some(Source::Path(common::input::day_path(dir, day)))),
+                (None, None) => (day, None),
+            })
+            .collect()
+    }
+
+    // The answers go with the inputs they came from.
+    fn answers_path(&self) -> PathBuf {
+        let input_dir = self
+            .input_dir
+            .clone()
+            .or_else(|| std::env::var_os(common::input::INPUT_DIR_VAR).map(PathBuf::from));
+
+        match (&self.answers, input_dir) {
+            (Some(path), _) => path.clone(),
+            (None, Some(dir)) => dir.join("answers"),
+            (None, None) => Path::new(env!("CARGO_MANIFEST_DIR"))
+                .parent()
+                .unwrap_or_else(|| Path::new("."))
+                .join("answers"),
+        }
+    }
+}
+
+// Set up the search budget and progress reports for a day about to run on
+// this thread.
+fn watch(day: usize, budget: Budget, show_progress: bool) {
+    progress::set_budget(budget);
+    if show_progress {
+        progress::set_hook(move |update| eprintln!("day {}: {}", day, update));
+    } else {
+        progress::clear_hook();
+    }
+}
+
+fn report_json<E: Display>(day: usize, report: &Result<DayReport, E>) -> String {
+    let report = match report {
+        Ok(report) => report,
+        Err(e) => {
+            return json::object(&[
+                ("day", day.to_string()),
+                ("error", json::string(&e.to_string())),
+            ])
+        }
+    };
+
+    let parts: Vec<String> = report
+        .parts
+        .iter()
+        .enumerate()
+        .map(|(n, part)| {
+            let diagnostics: Vec<(&str, String)> = part
+                .diagnostics
+                .iter()
+                .map(|(key, value)| (key.as_str(), json::value(value)))
+                .collect();
+            json::object(&[
+                ("part", (n + 1).to_string()),
+                ("answer", json::string(&part.answer)),
+                ("time_ns", part.time.as_nanos().to_string()),
+                ("diagnostics", json::object(&diagnostics)),
+            ])
+        })
+        .collect();
+
+    json::object(&[
+        ("day", day.to_string()),
+        ("parse_ns", report.parse_time.as_nanos().to_string()),
+        ("parts", format!("[{}]", parts.join(","))),
+    ])
+}
+
+// Like run(), but every day at once on a pool of threads.  A day that panics
+// or runs out of time is reported like any other error.
+fn run_parallel(options: &mut Options) {
+    let threads = options
+        .jobs
+        .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()));
+    let sources = options.sources();
+    let days: Vec<usize> = sources.iter().map(|&(day, _)| day).collect();
+    let (budget, show_progress) = (options.budget, options.progress);
+    let jobs = sources
+        .into_iter()
+        .map(|(day, source)| {
+            // Errors aren't Send, so they come back as their message.
+            Box::new(move || {
+                watch(day, budget, show_progress);
+                (DAYS[day - 1].report)(source).map_err(|e| e.to_string())
+            }) as Box<dyn FnOnce() -> Result<DayReport, String> + Send>
+        })
+        .collect();
+
+    let start = Instant::now();
+    let results = pool::run_all(jobs, threads, options.timeout);
+    let total = start.elapsed();
+
+    let mut failed = 0;
+    let reports: Vec<(usize, Result<DayReport, String>, Duration)> = days
+        .into_iter()
+        .zip(results)
+        .map(|(day, finished)| {
+            let report = match finished.outcome {
+                Outcome::Done(report) => report,
+                Outcome::Panicked(message) => Err(format!("panicked: {}", message)),
+                Outcome::TimedOut => Err(format!("timed out after {:.2?}", finished.time)),
+            };
+            if report.is_err() {
+                failed += 1;
+            }
+            (day, report, finished.time)
+        })
+        .collect();
+    let busy: Duration = reports.iter().map(|(_, _, time)| *time).sum();
+
+    let summary = format!(
+        "{} day(s) on {} thread(s) in {:.2?}, {:.2?} of solving, {} failed",
+        reports.len(),
+        threads,
+        total,
+        busy,
+        failed
+    );
+
+    if options.json {
+        let days: Vec<String> = reports
+            .iter()
+            .map(|(day, report, _)| report_json(*day, report))
+            .collect();
+        println!("{}", json::array(&days));
+        // Keep stdout the same shape as without --parallel.
+        eprintln!("{}", summary);
+        return;
+    }
+
+    let rows: Vec<Vec<String>> = reports
+        .into_iter()
+        .map(|(day, report, time)| {
+            let mut row = vec![day.to_string(), format!("{:.2?}", time)];
+            match report {
+                Ok(report) => row.extend(report.parts.into_iter().map(|part| part.answer)),
+                Err(e) => row.push(format!("error: {}", e)),
+            }
+            row
+        })
+        .collect();
+
+    table::print(&["Day", "Time", "Part 1", "Part 2"], &rows);
+    println!("\n{}", summary);
+}
+
+fn run(options: &mut Options) {
+    if options.parallel {
+        run_parallel(options);
+        return;
+    }
+
+    if options.json {
+        let days: Vec<String> = options
+            .sources()
+            .into_iter()
+            .map(|(day, source)| {
+                watch(day, options.budget, options.progress);
+                report_json(day, &(DAYS[day - 1].report)(source))
+            })
+            .collect();
+        println!("{}", json::array(&days));
+        return;
+    }
+
+    let rows: Vec<Vec<String>> = options
+        .sources()
+        .into_iter()
+        .map(|(day, source)| {
+            let mut row = vec![day.to_string()];
+            watch(day, options.budget, options.progress);
+            match (DAYS[day - 1].run)(source) {
+                Ok(answers) => row.extend(answers),
+                Err(e) => row.push(format!("error: {}", e)),
+            }
+            row
+        })
+        .collect();
+
+    table::print(&["Day", "Part 1", "Part 2"], &rows);
+}
+
+fn stats_json(stats: &Stats) -> String {
+    json::object(&[
+        ("min_ns", stats.min.as_nanos().to_string()),
+        ("median_ns", stats.median.as_nanos().to_string()),
+        ("max_ns", stats.max.as_nanos().to_string()),
+    ])
+}
+
+fn bench_all(options: &mut Options) {
+    let runs = options.runs;
+    let reports: Vec<(usize, Result<Report, Error>)> = options
+        .sources()
+        .into_iter()
+        .map(|(day, source)| (day, (DAYS[day - 1].bench)(source, runs)))
+        .collect();
+
+    if options.json {
+        let days: Vec<String> = reports
+            .iter()
+            .map(|(day, report)| match report {
+                Ok(report) => json::object(&[
+                    ("day", day.to_string()),
+                    ("runs", report.runs.to_string()),
+                    ("parse", stats_json(&report.parse)),
+                    ("part1", stats_json(&report.part1)),
+                    ("part2", stats_json(&report.part2)),
+                ]),
+                Err(e) => json::object(&[
+                    ("day", day.to_string()),
+                    ("error", json::string(&e.to_string())),
+                ]),
+            })
+            .collect();
+        println!("{}", json::array(&days));
+        return;
+    }
+
+    let cell = |stats: &Stats| {
+        format!(
+            "{:.2?} / {:.2?} / {:.2?}",
+            stats.min, stats.median, stats.max
+        )
+    };
+    let rows: Vec<Vec<String>> = reports
+        .iter()
+        .map(|(day, report)| match report {
+            Ok(report) => vec![
+                day.to_string(),
+                cell(&report.parse),
+                cell(&report.part1),
+                cell(&report.part2),
+            ],
+            Err(e) => vec![day.to_string(), format!("error: {}", e)],
+        })
+        .collect();
+
+    table::print(&["Day", "Parse", "Part 1", "Part 2"], &rows);
+    println!("\nmin / median / max over {} runs", runs);
+}
+
+fn verify(options: &mut Options) -> Result<(), Error> {
+    let path = options.answers_path();
+    let expected = Answers::load(&path)?;
+    let mut passed = 0;
+    let mut failed = 0;
+    let mut missing = vec![];
+
+    for (day, source) in options.sources() {
+        let answers = match (DAYS[day - 1].run)(source) {
+            Ok(answers) => answers,
+            Err(e) => {
+                println!("ERROR    day {}: {}", day, e);
+                failed += 1;
+                continue;
+            }
+        };
+
+        for (n, got) in answers.iter().enumerate() {
+            let part = n + 1;
+            match expected.get(day, part) {
+                Some(want) if want == got => {
+                    println!("PASS     day {} part {}", day, part);
+                    passed += 1;
+                }
+                Some(want) => {
+                    println!("FAIL     day {} part {}", day, part);
+                    for line in answers::diff(want, got) {
+                        println!("    {}", line);
+                    }
+                    failed += 1;
+                }
+                None => {
+                    println!("MISSING  day {} part {}", day, part);
+                    missing.push((day, part, got.clone()));
+                }
+            }
+        }
+    }
+
+    println!(
+        "\n{} passed, {} failed, {} missing",
+        passed,
+        failed,
+        missing.len()
+    );
+
+    if options.save && !missing.is_empty() {
+        Answers::append(&path, &missing)?;
+        println!("saved {} answer(s) to {}", missing.len(), path.display());
+    }
+
+    if failed > 0 {
+        Err(Box::new(VerifyError(failed)))
+    } else {
+        Ok(())
+    }
+}
+
+// Like verify(), but against the examples in the puzzle text rather than
+// our own inputs.
+fn examples(options: &Options) -> Result<(), Error> {
+    let mut passed = 0;
+    let mut failed = 0;
+
+    for &day in &options.days {
+        let checked = (DAYS[day - 1].examples)();
+        if checked.is_empty() {
+            println!("NONE     day {}", day);
+        }
+
+        for checked in checked {
+            let name = format!(
+                "day {} example {} part {}",
+                day, checked.example, checked.part
+            );
+            match &checked.answer {
+                Ok(_) if checked.passed() => {
+                    println!("PASS     {}", name);
+                    passed += 1;
+                }
+                Ok(got) => {
+                    println!("FAIL     {}", name);
+                    for line in answers::diff(checked.expected, got) {
+                        println!("    {}", line);
+                    }
+                    failed += 1;
+                }
+                Err(e) => {
+                    println!("ERROR    {}: {}", name, e);
+                    failed += 1;
+                }
+            }
+        }
+    }
+
+    println!("\n{} passed, {} failed", passed, failed);
+
+    if failed > 0 {
+        Err(Box::new(VerifyError(failed)))
+    } else {
+        Ok(())
+    }
+}
+
+// Make up an input for each day, printing it or writing it to --input-dir
+// where `aoc run --input-dir` will find it.
+fn generate(options: &Options) -> Result<(), Error> {
+    if options.input_dir.is_none() && options.days.len() > 1 {
+        return Err(Box::new(UsageError::Output));
+    }
+
+    let seed = options.seed.unwrap_or_else(Rng::time_seed);
+    // On stderr, so it doesn't end up in the input.
+    eprintln!("seed {}", seed);
+
+    for &day in &options.days {
+        // The same seed for every day, so generating them all gives the same
+        // input for a day as generating it on its own.
+        let lines = (DAYS[day - 1].generate)(&mut Rng::new(seed), options.size);
+        let text: String = lines.iter().map(|line| format!("{}\n", line)).collect();
+
+        match &options.input_dir {
+            Some(dir) => {
+                std::fs::create_dir_all(dir)?;
+                let path = common::input::day_path(dir, day);
+                std::fs::write(&path, text)?;
+                eprintln!("wrote {}", path.display());
+            }
+            None => print!("{}", text),
+        }
+    }
+
+    Ok(())
+}
+
+// The one day to show or export, and its simulation.
+fn load_simulation(options: &mut Options) -> Result<(usize, Box<dyn Simulation>), Error> {
+    if options.days.len() > 1 {
+        return Err(Box::new(UsageError::OneDay));
+    }
+    let (day, source) = options.sources().remove(0);
+    let simulation = DAYS[day - 1].simulation.ok_or(UsageError::Show(day))?;
+
+    Ok((day, simulation(source)?))
+}
+
+// Step through one of the simulation days in the terminal.
+fn show(options: &mut Options) -> Result<(), Error> {
+    if matches!(options.input, Some(Source::Stdin)) {
+        return Err(Box::new(UsageError::Interactive));
+    }
+    let (day, simulation) = load_simulation(options)?;
+
+    show::show(&format!("day {}", day), simulation, options.delay);
+
+    Ok(())
+}
+
+// Save pictures of a simulation day instead.
+fn export(options: &mut Options) -> Result<(), Error> {
+    let path = options.output.clone().ok_or(UsageError::NoOutput)?;
+    let (_, simulation) = load_simulation(options)?;
+
+    let frames = show::export(
+        simulation,
+        &path,
+        options.scale,
+        options.delay,
+        options.steps,
+    )?;
+    eprintln!("wrote {} frame(s) to {}", frames, path.display());
+
+    Ok(())
+}
+
+fn try_main(args: &[String]) -> Result<(), Error> {
+    match args.split_first() {
+        Some((command, rest)) if command == "run" => {
+            let flags = [
+                "--input",
+                "--input-dir",
+                "--json",
+                "--parallel",
+                "--jobs",
+                "--timeout",
+                "--budget",
+                "--max-steps",
+                "--progress",
+            ];
+            let mut options = Options::parse(rest, &flags)?;
+            run(&mut options);
+            Ok(())
+        }
+        Some((command, rest)) if command == "bench" => {
+            let flags = ["--input", "--input-dir", "--runs", "--json"];
+            let mut options = Options::parse(rest, &flags)?;
+            bench_all(&mut options);
+            Ok(())
+        }
+        Some((command, rest)) if command == "verify" => {
+            let flags = ["--input", "--input-dir", "--answers", "--save"];
+            let mut options = Options::parse(rest, &flags)?;
+            verify(&mut options)
+        }
+        Some((command, rest)) if command == "example" => examples(&Options::parse(rest, &[])?),
+        Some((command, rest)) if command == "generate" => {
+            let flags = ["--seed", "--size", "--input-dir"];
+            generate(&Options::parse(rest, &flags)?)
+        }
+        Some((command, rest)) if command == "show" => {
+            let flags = ["--delay", "--input", "--input-dir"];
+            show(&mut Options::parse(rest, &flags)?)
+        }
+        Some((command, rest)) if command == "export" => {
+            let flags = [
+                "--output",
+                "--scale",
+                "--steps",
+                "--delay",
+                "--input",
+                "--input-dir",
+            ];
+            export(&mut Options::parse(rest, &flags)?)
+        }
+        _ => Err(Box::new(UsageError::Command)),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    // Returning the error from main() would print its Debug form, which isn't much use here.
+    if let Err(e) = try_main(&args) {
+        eprintln!("aoc: {}", e);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,214 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+type Job = Box<dyn FnOnce() + Send>;
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome<T> {
+    Done(T),
+    // what it panicked with
+    Panicked(String),
+    TimedOut,
+}
+
+// How a job went, and how long it ran for (or had run for when we gave up).
+#[derive(Debug)]
+pub struct Finished<T> {
+    pub outcome: Outcome<T>,
+    pub time: Duration,
+}
+
+enum Event<T> {
+    Started(usize, Instant),
+    Finished(usize, Result<T, String>, Duration),
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<&str>() {
+        Ok(s) => s.to_string(),
+        Err(payload) => match payload.downcast::<String>() {
+            Ok(s) => *s,
+            Err(_) => String::from("unknown panic"),
+        },
+    }
+}
+
+// A thread taking jobs off the shared queue until it's empty and closed.
+fn spawn_worker(queue: Arc<Mutex<Receiver<Job>>>) {
+    thread::spawn(move || loop {
+        // The lock is only held while waiting for a job, never while running
+        // one, so a panicking job can't poison it.
+        let job = match queue.lock() {
+            Ok(queue) => queue.recv(),
+            Err(_) => break,
+        };
+        match job {
+            Ok(job) => job(),
+            Err(_) => break,
+        }
+    });
+}
+
+// Run the jobs on `threads` threads and wait for them all, catching any that
+// panic and giving up on any that run longer than `timeout`.  Outcomes come
+// back in the same order as the jobs.
+//
+// There's no way to stop a thread from outside, so a job we've given up on
+// keeps going in the background and holds on to its thread.  Another thread
+// is started to take its place, and the stragglers die with the process.
+pub fn run_all<T: Send + 'static>(
+    jobs: Vec<Box<dyn FnOnce() -> T + Send>>,
+    threads: usize,
+    timeout: Option<Duration>,
+) -> Vec<Finished<T>> {
+    let count = jobs.len();
+    let (queue, receiver) = mpsc::channel::<Job>();
+    let receiver = Arc::new(Mutex::new(receiver));
+    let (sender, events) = mpsc::channel::<Event<T>>();
+
+    for (n, job) in jobs.into_iter().enumerate() {
+        let sender = sender.clone();
+        // Nobody's listening if we've already given up on this job.
+        let _ = queue.send(Box::new(move || {
+            let start = Instant::now();
+            let _ = sender.send(Event::Started(n, start));
+            let result = panic::catch_unwind(AssertUnwindSafe(job)).map_err(panic_message);
+            let _ = sender.send(Event::Finished(n, result, start.elapsed()));
+        }));
+    }
+    // With these gone, workers stop once the queue is empty, and `events`
+    // disconnects once every job has been run.
+    drop(queue);
+    drop(sender);
+
+    for _ in 0..threads.clamp(1, count.max(1)) {
+        spawn_worker(receiver.clone());
+    }
+
+    let mut started: Vec<Option<Instant>> = vec![None; count];
+    let mut finished: Vec<Option<Finished<T>>> = (0..count).map(|_| None).collect();
+    let mut remaining = count;
+
+    while remaining > 0 {
+        // The soonest any running job will run out of time.
+        let deadline = timeout.and_then(|timeout| {
+            (0..count)
+                .filter(|&n| finished[n].is_none())
+                .filter_map(|n| started[n])
+                .map(|start| start + timeout)
+                .min()
+        });
+        let event = match deadline {
+            Some(deadline) => {
+                events.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            }
+            None => events.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        match event {
+            Ok(Event::Started(n, start)) => started[n] = Some(start),
+            // It might have finished after we gave up on it.
+            Ok(Event::Finished(n, result, time)) if finished[n].is_none() => {
+                let outcome = match result {
+                    Ok(value) => Outcome::Done(value),
+                    Err(message) => Outcome::Panicked(message),
+                };
+                finished[n] = Some(Finished { outcome, time });
+                remaining -= 1;
+            }
+            Ok(Event::Finished(..)) => {}
+            Err(RecvTimeoutError::Timeout) => {
+                let now = Instant::now();
+                for (n, start) in started.iter().enumerate() {
+                    let (Some(start), Some(timeout)) = (start, timeout) else {
+                        continue;
+                    };
+                    if finished[n].is_none() && *start + timeout <= now {
+                        finished[n] = Some(Finished {
+                            outcome: Outcome::TimedOut,
+                            time: now - *start,
+                        });
+                        remaining -= 1;
+                        spawn_worker(receiver.clone());
+                    }
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    finished
+        .into_iter()
+        .map(|finished| {
+            // Only if a job was dropped without ever running, which shouldn't happen.
+            finished.unwrap_or(Finished {
+                outcome: Outcome::Panicked(String::from("never ran")),
+                time: Duration::ZERO,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn order_test() {
+        let jobs: Vec<Box<dyn FnOnce() -> usize + Send>> = (0..20usize)
+            .map(|n| {
+                Box::new(move || {
+                    // Make the early jobs finish last.
+                    thread::sleep(Duration::from_millis(20 - n as u64));
+                    n * 2
+                }) as Box<dyn FnOnce() -> usize + Send>
+            })
+            .collect();
+
+        let results = run_all(jobs, 4, None);
+
+        assert!(results.len() == 20);
+        for (n, result) in results.iter().enumerate() {
+            assert!(result.outcome == Outcome::Done(n * 2));
+        }
+    }
+
+    #[test]
+    fn panic_test() {
+        let jobs: Vec<Box<dyn FnOnce() -> usize + Send>> = vec![
+            Box::new(|| 1),
+            Box::new(|| panic!("day {} broke", 2)),
+            Box::new(|| 3),
+        ];
+
+        let results = run_all(jobs, 1, None);
+
+        assert!(results[0].outcome == Outcome::Done(1));
+        assert!(results[1].outcome == Outcome::Panicked(String::from("day 2 broke")));
+        assert!(results[2].outcome == Outcome::Done(3));
+    }
+
+    #[test]
+    fn timeout_test() {
+        let jobs: Vec<Box<dyn FnOnce() -> usize + Send>> = vec![
+            Box::new(|| {
+                thread::sleep(Duration::from_secs(5));
+                1
+            }),
+            Box::new(|| 2),
+        ];
+
+        // Even with one thread, the slow job doesn't hold up the other.
+        let start = Instant::now();
+        let results = run_all(jobs, 1, Some(Duration::from_millis(50)));
+
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(results[0].outcome == Outcome::TimedOut);
+        assert!(results[0].time >= Duration::from_millis(50));
+        assert!(results[1].outcome == Outcome::Done(2));
+    }
+}
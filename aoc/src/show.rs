@@ -0,0 +1,143 @@
+use common::image::{self, Colour};
+use common::visualise::{Command, Player, COMMANDS};
+use common::{Grid, Simulation};
+use std::io::{BufRead, Write};
+use std::path::Path;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+// Clear the screen and go back to the top left.
+const CLEAR: &str = "\x1b[2J\x1b[H";
+
+fn draw(title: &str, player: &Player, playing: bool, message: &str) {
+    let last = match player.last() {
+        Some(last) => last.to_string(),
+        None => String::from("?"),
+    };
+    let state = if playing { "playing" } else { "paused" };
+
+    print!(
+        "{}{}  step {}/{}  {}\n\n{}\n\n{}\n{}\n> ",
+        CLEAR,
+        title,
+        player.current(),
+        last,
+        state,
+        player.frame(),
+        message,
+        COMMANDS
+    );
+    let _ = std::io::stdout().flush();
+}
+
+// Watch a simulation in the terminal, a command per line on stdin.  While
+// playing, it moves on a step every `delay` until it's told otherwise or
+// runs out of steps.
+pub fn show(title: &str, simulation: Box<dyn Simulation>, delay: Duration) {
+    let mut player = Player::new(simulation);
+    let mut playing = false;
+    let mut message = String::new();
+
+    // Reading stdin blocks, so it gets a thread of its own and we can still
+    // keep playing while waiting for the next command.
+    let (sender, commands) = mpsc::channel();
+    thread::spawn(move || {
+        for line in std::io::stdin().lock().lines() {
+            let Ok(line) = line else { break };
+            if sender.send(line.parse::<Command>()).is_err() {
+                break;
+            }
+        }
+    });
+
+    loop {
+        draw(title, &player, playing, &message);
+        message.clear();
+
+        let command = if playing {
+            match commands.recv_timeout(delay) {
+                Ok(command) => command,
+                Err(RecvTimeoutError::Timeout) => {
+                    playing = player.forward();
+                    continue;
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        } else {
+            match commands.recv() {
+                Ok(command) => command,
+                Err(_) => break,
+            }
+        };
+
+        match command {
+            Ok(Command::Forward) => {
+                playing = false;
+                if !player.forward() {
+                    message = String::from("that's the last step");
+                }
+            }
+            Ok(Command::Back) => {
+                playing = false;
+                if !player.back() {
+                    message = String::from("that's the first step");
+                }
+            }
+            Ok(Command::Play) => playing = !playing && !player.at_end(),
+            Ok(Command::Jump(n)) => {
+                playing = false;
+                if player.jump(n) != n {
+                    message = format!("it's all over by step {}", player.current());
+                }
+            }
+            Ok(Command::Quit) => break,
+            Err(e) => message = e.to_string(),
+        }
+    }
+    println!();
+}
+
+#[derive(Debug)]
+pub struct NoPictureError;
+
+impl std::error::Error for NoPictureError {}
+
+impl std::fmt::Display for NoPictureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "this simulation only draws in text")
+    }
+}
+
+// Save a picture of every step, up to `steps` of them: as an animated GIF if
+// the path ends in .gif, otherwise as numbered PNGs in that directory.
+// Returns how many frames there were.
+pub fn export(
+    mut simulation: Box<dyn Simulation>,
+    path: &Path,
+    scale: usize,
+    delay: Duration,
+    steps: usize,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let mut frames = vec![simulation.picture().ok_or(NoPictureError)?];
+    while frames.len() <= steps && simulation.step() {
+        frames.extend(simulation.picture());
+    }
+
+    if path.extension().is_some_and(|extension| extension == "gif") {
+        let frames: Vec<Grid<Colour>> = image::fit(&frames)
+            .iter()
+            .map(|frame| image::scale(frame, scale))
+            .collect();
+        let centiseconds = (delay.as_millis() / 10).min(u16::MAX as u128) as u16;
+        std::fs::write(path, image::gif(&frames, centiseconds)?)?;
+    } else {
+        std::fs::create_dir_all(path)?;
+        for (n, frame) in frames.iter().enumerate() {
+            let png = image::png(&image::scale(frame, scale));
+            std::fs::write(path.join(format!("{:04}.png", n)), png)?;
+        }
+    }
+
+    Ok(frames.len())
+}
@@ -0,0 +1,193 @@
+use crate::{Solution, Source};
+use std::str::FromStr;
+
+// One of the commands a day's binary has on top of answering the puzzle:
+// flags on their own, flags with a value after them, and arguments of its own
+// before the input path.
+pub struct Subcommand {
+    pub name: &'static str,
+    pub switches: &'static [&'static str],
+    pub values: &'static [&'static str],
+    pub arguments: &'static [&'static str],
+}
+
+// Without a command, all a binary takes is the path.
+const ANSWER: Subcommand = Subcommand {
+    name: "",
+    switches: &[],
+    values: &[],
+    arguments: &[],
+};
+
+#[derive(Debug, PartialEq)]
+pub enum ArgsError {
+    Flag(String),
+    // a flag with nothing after it
+    Missing(String),
+    // an argument that wasn't given
+    Argument(&'static str),
+    // the flag or argument, and what it was given
+    Value(String, String),
+    // anything after the path
+    Extra(String),
+}
+
+impl std::error::Error for ArgsError {}
+
+impl std::fmt::Display for ArgsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ArgsError::Flag(flag) => write!(f, "unexpected flag {}", flag),
+            ArgsError::Missing(flag) => write!(f, "{} needs a value", flag),
+            ArgsError::Argument(name) => write!(f, "expected <{}>", name),
+            ArgsError::Value(name, value) => write!(f, "\"{}\" won't do for {}", value, name),
+            ArgsError::Extra(arg) => write!(f, "unexpected argument \"{}\"", arg),
+        }
+    }
+}
+
+// A day's command line, checked against what its command takes.
+#[derive(Debug)]
+pub struct Args {
+    command: Option<&'static str>,
+    switches: Vec<&'static str>,
+    // flags and arguments alike, by name
+    values: Vec<(&'static str, String)>,
+    path: Option<String>,
+}
+
+impl Args {
+    pub fn parse(
+        args: impl IntoIterator<Item = String>,
+        commands: &[Subcommand],
+    ) -> Result<Args, ArgsError> {
+        let mut args = args.into_iter().peekable();
+        let command = args
+            .peek()
+            .and_then(|arg| commands.iter().find(|command| command.name == arg));
+        if command.is_some() {
+            args.next();
+        }
+        let command = command.unwrap_or(&ANSWER);
+        let mut parsed = Args {
+            command: Some(command.name).filter(|name| !name.is_empty()),
+            switches: vec![],
+            values: vec![],
+            path: None,
+        };
+        let mut arguments = command.arguments.iter();
+
+        while let Some(arg) = args.next() {
+            let named = |names: &[&'static str]| names.iter().copied().find(|&name| name == arg);
+
+            if let Some(flag) = named(command.switches) {
+                parsed.switches.push(flag);
+            } else if let Some(flag) = named(command.values) {
+                let value = args.next().ok_or(ArgsError::Missing(arg))?;
+                parsed.values.push((flag, value));
+            } else if arg.starts_with("--") {
+                return Err(ArgsError::Flag(arg));
+            } else if let Some(&name) = arguments.next() {
+                parsed.values.push((name, arg));
+            } else if parsed.path.is_none() {
+                parsed.path = Some(arg);
+            } else {
+                return Err(ArgsError::Extra(arg));
+            }
+        }
+
+        match arguments.next() {
+            Some(&name) => Err(ArgsError::Argument(name)),
+            None => Ok(parsed),
+        }
+    }
+
+    pub fn command(&self) -> Option<&str> {
+        self.command
+    }
+
+    pub fn switch(&self, flag: &str) -> bool {
+        self.switches.contains(&flag)
+    }
+
+    // The flag's value (the last one, if it was given more than once), or an
+    // argument's.
+    pub fn value<T: FromStr>(&self, name: &str) -> Result<Option<T>, ArgsError> {
+        let Some((_, value)) = self.values.iter().rev().find(|(n, _)| *n == name) else {
+            return Ok(None);
+        };
+
+        match value.parse() {
+            Ok(value) => Ok(Some(value)),
+            Err(_) => Err(ArgsError::Value(name.to_string(), value.clone())),
+        }
+    }
+
+    // One of the command's arguments, which parse() made sure is there.
+    pub fn argument<T: FromStr>(&self, name: &'static str) -> Result<T, ArgsError> {
+        self.value(name)?.ok_or(ArgsError::Argument(name))
+    }
+
+    // An explicit path, or `-` for stdin.  Otherwise see input::locate.
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    pub fn source(&self) -> Option<Source> {
+        self.path().map(Source::from)
+    }
+}
+
+// The binary's own arguments, or the usage and an exit if they don't make
+// sense.
+pub fn parse<S: Solution>(commands: &[Subcommand], usage: &str) -> Args {
+    Args::parse(std::env::args().skip(1), commands).unwrap_or_else(|e| {
+        eprintln!("day{}: {}\n\n{}", S::DAY, e, usage);
+        std::process::exit(2);
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const COMMANDS: &[Subcommand] = &[Subcommand {
+        name: "replay",
+        switches: &["--json"],
+        values: &["--output"],
+        arguments: &["step"],
+    }];
+
+    fn parse(args: &str) -> Result<Args, ArgsError> {
+        Args::parse(args.split_whitespace().map(String::from), COMMANDS)
+    }
+
+    #[test]
+    fn args_test() {
+        let args = parse("replay --json 12 --output a --output b input").unwrap();
+        assert!(args.command() == Some("replay") && args.switch("--json"));
+        assert!(args.argument::<usize>("step") == Ok(12));
+        assert!(args.value::<String>("--output") == Ok(Some("b".to_string())));
+        assert!(args.path() == Some("input"));
+
+        let args = parse("-").unwrap();
+        assert!(args.command().is_none() && args.path() == Some("-"));
+        assert!(!args.switch("--json") && args.value::<usize>("step") == Ok(None));
+
+        let e = parse("replay x")
+            .unwrap()
+            .value::<usize>("step")
+            .unwrap_err();
+        assert!(e == ArgsError::Value("step".to_string(), "x".to_string()));
+    }
+
+    #[test]
+    fn args_error_test() {
+        assert!(parse("--json").unwrap_err() == ArgsError::Flag("--json".to_string()));
+        assert!(
+            parse("replay 1 --output").unwrap_err() == ArgsError::Missing("--output".to_string())
+        );
+        assert!(parse("replay --json").unwrap_err() == ArgsError::Argument("step"));
+        assert!(parse("one two").unwrap_err() == ArgsError::Extra("two".to_string()));
+    }
+}
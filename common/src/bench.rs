@@ -0,0 +1,88 @@
+use crate::{report, Solution};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    fn from(mut samples: Vec<Duration>) -> Stats {
+        assert!(!samples.is_empty());
+        samples.sort();
+
+        // With an even number of runs we take the upper of the middle two,
+        // which saves averaging Durations and is close enough for this.
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Report {
+    pub runs: usize,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+// Time parsing and each part separately, `runs` times over.  Copying the
+// input lines for each parse isn't counted.
+pub fn bench<S: Solution>(
+    lines: &[String],
+    runs: usize,
+) -> Result<Report, Box<dyn std::error::Error>> {
+    let runs = runs.max(1);
+    let mut parse = vec![];
+    let mut part1 = vec![];
+    let mut part2 = vec![];
+
+    for _ in 0..runs {
+        let lines = lines.to_vec();
+
+        let start = Instant::now();
+        let input = S::parse(black_box(lines))?;
+        parse.push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(S::part1(&input)?);
+        part1.push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(S::part2(&input)?);
+        part2.push(start.elapsed());
+
+        // Nobody wants the same note a hundred times over, or to keep
+        // whatever a note was going to be worked out from.
+        report::discard_notes();
+    }
+
+    Ok(Report {
+        runs,
+        parse: Stats::from(parse),
+        part1: Stats::from(part1),
+        part2: Stats::from(part2),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn stats_test() {
+        let ms = Duration::from_millis;
+        let stats = Stats::from(vec![ms(5), ms(1), ms(3), ms(9)]);
+
+        assert!(stats.min == ms(1));
+        assert!(stats.median == ms(5));
+        assert!(stats.max == ms(9));
+        assert!(Stats::from(vec![ms(2)]).median == ms(2));
+    }
+}
@@ -0,0 +1,80 @@
+use crate::example::answer;
+use crate::{Reference, Rng};
+use std::fmt::Display;
+
+// How many seeds to try at each size.
+pub const SEEDS: u64 = 20;
+
+// A generated input the fast and reference solutions answered differently.
+#[derive(Debug)]
+pub struct Disagreement {
+    pub day: usize,
+    pub seed: u64,
+    pub size: usize,
+    pub part: usize,
+    // the answers, or why there wasn't one
+    pub reference: Result<String, String>,
+    pub fast: Result<String, String>,
+    pub input: Vec<String>,
+}
+
+impl Display for Disagreement {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let show = |answer: &Result<String, String>| match answer {
+            Ok(answer) => format!("{:?}", answer),
+            Err(e) => format!("an error: {}", e),
+        };
+
+        writeln!(
+            f,
+            "day {} part {}, seed {}, size {}: the reference gave {} but the fast one gave {}",
+            self.day,
+            self.part,
+            self.seed,
+            self.size,
+            show(&self.reference),
+            show(&self.fast)
+        )?;
+        write!(f, "{}", self.input.join("\n"))
+    }
+}
+
+// Generate inputs and solve each one both ways, stopping at the first one
+// they don't agree on.  Every seed is tried at one size before moving on to
+// the next, so with the sizes smallest first, the disagreement that comes
+// back is as small as we could find.
+pub fn differ<S: Reference>(seeds: u64, sizes: &[usize]) -> Option<Disagreement> {
+    for &size in sizes {
+        for seed in 0..seeds {
+            let lines = S::generate(&mut Rng::new(seed), Some(size));
+            let input = S::parse(lines.clone()).map_err(|e| e.to_string());
+
+            let parts = [
+                (answer(&input, S::reference1), answer(&input, S::part1)),
+                (answer(&input, S::reference2), answer(&input, S::part2)),
+            ];
+            for (n, (reference, fast)) in parts.into_iter().enumerate() {
+                if reference != fast {
+                    return Some(Disagreement {
+                        day: S::DAY,
+                        seed,
+                        size,
+                        part: n + 1,
+                        reference,
+                        fast,
+                        input: lines,
+                    });
+                }
+            }
+        }
+    }
+
+    None
+}
+
+// For tests: fail with the first disagreement, input and all.
+pub fn assert_agree<S: Reference>(sizes: &[usize]) {
+    if let Some(disagreement) = differ::<S>(SEEDS, sizes) {
+        panic!("{}", disagreement);
+    }
+}
@@ -0,0 +1,95 @@
+use crate::Solution;
+use std::fmt::Display;
+
+// One of the worked examples from the puzzle text, and the answers the text
+// gives for it.  Some examples only come with an answer for one part.
+pub struct Example {
+    pub input: &'static str,
+    pub part1: Option<&'static str>,
+    pub part2: Option<&'static str>,
+}
+
+// What a day made of one part of one of its examples.
+#[derive(Debug)]
+pub struct Checked {
+    // both counting from 1
+    pub example: usize,
+    pub part: usize,
+    pub expected: &'static str,
+    // or why there wasn't an answer
+    pub answer: Result<String, String>,
+}
+
+impl Checked {
+    pub fn passed(&self) -> bool {
+        self.answer.as_deref() == Ok(self.expected)
+    }
+}
+
+impl Display for Checked {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "example {} part {}: ", self.example, self.part)?;
+        match &self.answer {
+            Ok(answer) if answer == self.expected => write!(f, "{}", answer),
+            Ok(answer) => write!(f, "expected {:?}, got {:?}", self.expected, answer),
+            Err(e) => write!(f, "expected {:?}, got an error: {}", self.expected, e),
+        }
+    }
+}
+
+// A part's answer or error as text, for comparing.
+pub(crate) fn answer<I, T: Display>(
+    input: &Result<I, String>,
+    part: fn(&I) -> Result<T, Box<dyn std::error::Error>>,
+) -> Result<String, String> {
+    let input = input.as_ref().map_err(|e| e.clone())?;
+    let answer = part(input)
+        .map(|answer| answer.to_string())
+        .map_err(|e| e.to_string());
+    // Nobody wants the notes from an example (or any other input we're only
+    // checking).
+    crate::report::discard_notes();
+
+    answer
+}
+
+// Solve every example, but only the parts the puzzle gives an answer for: a
+// part 1 example can be too small (or too big) to make sense for part 2.
+pub fn check<S: Solution>() -> Vec<Checked> {
+    let mut checked = vec![];
+
+    for (n, example) in S::EXAMPLES.iter().enumerate() {
+        let lines = example.input.lines().map(String::from).collect();
+        let input = S::parse(lines).map_err(|e| e.to_string());
+
+        if let Some(expected) = example.part1 {
+            checked.push(Checked {
+                example: n + 1,
+                part: 1,
+                expected,
+                answer: answer(&input, S::part1),
+            });
+        }
+        if let Some(expected) = example.part2 {
+            checked.push(Checked {
+                example: n + 1,
+                part: 2,
+                expected,
+                answer: answer(&input, S::part2),
+            });
+        }
+    }
+
+    checked
+}
+
+// For tests: fail listing everything that didn't match.
+pub fn assert_examples<S: Solution>() {
+    let failed: Vec<String> = check::<S>()
+        .iter()
+        .filter(|checked| !checked.passed())
+        .map(|checked| checked.to_string())
+        .collect();
+
+    assert!(failed.is_empty(), "day {}:\n{}", S::DAY, failed.join("\n"));
+}
@@ -0,0 +1,34 @@
+use crate::{Rng, Solution};
+
+// A `width` x `height` map of random digits between `min` and `max`, like
+// the grid days take.
+pub fn digits(rng: &mut Rng, width: usize, height: usize, min: u32, max: u32) -> Vec<String> {
+    (0..height)
+        .map(|_| {
+            (0..width)
+                .map(|_| char::from_digit(rng.between(min as i64, max as i64) as u32, 10).unwrap())
+                .collect()
+        })
+        .collect()
+}
+
+// For tests: whatever the day generates, it has to be able to parse.  Sizes
+// of 1 catch generators that assume there's more than one of something.
+pub fn assert_generated<S: Solution>() {
+    for seed in 0..5 {
+        for size in [Some(1), Some(5), None] {
+            let lines = S::generate(&mut Rng::new(seed), size);
+
+            if let Err(e) = S::parse(lines.clone()) {
+                panic!(
+                    "day {}, seed {}, size {:?}: {}\n{}",
+                    S::DAY,
+                    seed,
+                    size,
+                    e,
+                    lines.join("\n")
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,301 @@
+use std::ops::{Index, IndexMut};
+
+// A rectangle of cells, stored flat a row at a time.  Positions are (x, y)
+// with (0, 0) in the top left, the way the puzzles draw them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    Empty,
+    // line, its width, and the width of the lines before it
+    Ragged(usize, usize, usize),
+    // line, column, and what we found there
+    Cell(usize, usize, char),
+}
+
+impl GridError {
+    // For a grid that starts partway through the input: move the line
+    // number down past the `lines` before it.
+    pub fn offset(self, lines: usize) -> GridError {
+        match self {
+            GridError::Empty => GridError::Empty,
+            GridError::Ragged(line, width, expected) => {
+                GridError::Ragged(line + lines, width, expected)
+            }
+            GridError::Cell(line, column, c) => GridError::Cell(line + lines, column, c),
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+impl std::fmt::Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "grid has no cells"),
+            GridError::Ragged(line, width, expected) => write!(
+                f,
+                "line {}: {} cells wide, but the lines before it are {}",
+                line, width, expected
+            ),
+            GridError::Cell(line, column, c) => {
+                write!(f, "line {}, column {}: unexpected \"{}\"", line, column, c)
+            }
+        }
+    }
+}
+
+// Offsets to the 4 orthogonal neighbours, then the 4 diagonals.
+const OFFSETS: [(isize, isize); 8] = [
+    (0, -1),
+    (-1, 0),
+    (1, 0),
+    (0, 1),
+    (-1, -1),
+    (1, -1),
+    (-1, 1),
+    (1, 1),
+];
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>, GridError> {
+        let width = rows.first().map_or(0, |row| row.len());
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+
+        for (n, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(GridError::Ragged(n + 1, row.len(), width));
+            }
+            cells.extend(row);
+        }
+
+        if cells.is_empty() {
+            return Err(GridError::Empty);
+        }
+
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    // Read a character map like the puzzles give us, turning each character
+    // into a cell.  Blank lines at the end are ignored.
+    pub fn parse<F>(lines: &[String], cell: F) -> Result<Grid<T>, GridError>
+    where
+        F: Fn(char) -> Option<T>,
+    {
+        let mut rows = vec![];
+        let end = lines
+            .iter()
+            .rposition(|line| !line.trim().is_empty())
+            .map_or(0, |n| n + 1);
+
+        for (y, line) in lines[..end].iter().enumerate() {
+            let row = line
+                .trim_end()
+                .chars()
+                .enumerate()
+                .map(|(x, c)| cell(c).ok_or(GridError::Cell(y + 1, x + 1, c)))
+                .collect::<Result<Vec<T>, GridError>>()?;
+            rows.push(row);
+        }
+
+        Grid::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    // Off one edge and back on the opposite one, like the grid is a torus.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+
+        &self[(x, y)]
+    }
+
+    // Every position, a row at a time.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+
+        (0..self.width * self.height).map(move |n| (n % width, n / width))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    // Every row, even in a grid 0 cells wide.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |y| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: Fn(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    fn neighbours(&self, x: usize, y: usize, count: usize) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width as isize, self.height as isize);
+
+        OFFSETS[..count].iter().filter_map(move |(dx, dy)| {
+            let (nx, ny) = (x as isize + dx, y as isize + dy);
+            if (0..width).contains(&nx) && (0..height).contains(&ny) {
+                Some((nx as usize, ny as usize))
+            } else {
+                None
+            }
+        })
+    }
+
+    // Up, left, right and down, leaving out any that are off the edge.
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbours(x, y, 4)
+    }
+
+    // As above plus the diagonals.
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbours(x, y, 8)
+    }
+
+    // One character per cell, one line per row.
+    pub fn render<F>(&self, cell: F) -> String
+    where
+        F: Fn(&T) -> char,
+    {
+        self.rows()
+            .map(|row| row.iter().map(&cell).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is off the grid",
+            x,
+            y
+        );
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is off the grid",
+            x,
+            y
+        );
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn digits(map: &str) -> Result<Grid<u32>, GridError> {
+        let lines: Vec<String> = map.lines().map(String::from).collect();
+        Grid::parse(&lines, |c| c.to_digit(10))
+    }
+
+    #[test]
+    fn parse_test() {
+        let grid = digits("123\n456\n\n").unwrap();
+
+        assert!(grid.width() == 3 && grid.height() == 2);
+        assert!(grid[(2, 1)] == 6);
+        assert!(grid.get(3, 0).is_none());
+        assert!(grid.rows().collect::<Vec<&[u32]>>() == vec![&[1, 2, 3], &[4, 5, 6]]);
+        assert!(grid.render(|d| char::from_digit(*d, 10).unwrap()) == "123\n456");
+
+        assert!(digits("123\n45") == Err(GridError::Ragged(2, 2, 3)));
+        assert!(digits("123\n4x6") == Err(GridError::Cell(2, 2, 'x')));
+        assert!(digits("\n") == Err(GridError::Empty));
+        assert!(digits("123\n4x6").unwrap_err().offset(2) == GridError::Cell(4, 2, 'x'));
+    }
+
+    #[test]
+    fn neighbours_test() {
+        let grid = Grid::new(3, 3, 0);
+
+        assert!(grid.neighbours4(1, 1).count() == 4);
+        assert!(grid.neighbours8(1, 1).count() == 8);
+        assert!(grid.neighbours4(0, 0).collect::<Vec<_>>() == vec![(1, 0), (0, 1)]);
+        assert!(grid.neighbours8(2, 2).collect::<Vec<_>>() == vec![(2, 1), (1, 2), (1, 1)]);
+    }
+
+    #[test]
+    fn empty_test() {
+        let grid = Grid::new(0, 2, 0);
+
+        assert!(grid.rows().all(|row| row.is_empty()) && grid.rows().count() == 2);
+        assert!(grid.render(|_| '#') == "\n");
+        assert!(Grid::new(3, 0, 0).rows().count() == 0);
+    }
+
+    #[test]
+    fn wrapping_test() {
+        let grid = digits("123\n456").unwrap();
+
+        assert!(*grid.get_wrapping(-1, 0) == 3);
+        assert!(*grid.get_wrapping(3, 2) == 1);
+        assert!(*grid.get_wrapping(-4, -1) == 6);
+    }
+}
@@ -0,0 +1,444 @@
+use crate::Grid;
+use std::collections::HashMap;
+
+// Just enough PNG and GIF to save pictures of the simulations, so there's no
+// need for an image crate or anything to convert them afterwards.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Colour(pub u8, pub u8, pub u8);
+
+impl Colour {
+    pub const BLACK: Colour = Colour(0, 0, 0);
+    pub const WHITE: Colour = Colour(255, 255, 255);
+
+    // Part of the way from one colour to another, `n` out of `out_of`.
+    pub fn blend(self, other: Colour, n: usize, out_of: usize) -> Colour {
+        let mix = |a: u8, b: u8| ((a as usize * (out_of - n) + b as usize * n) / out_of) as u8;
+
+        Colour(
+            mix(self.0, other.0),
+            mix(self.1, other.1),
+            mix(self.2, other.2),
+        )
+    }
+}
+
+// A GIF can only have 256 colours.
+#[derive(Debug, PartialEq)]
+pub struct ColoursError(pub usize);
+
+impl std::error::Error for ColoursError {}
+
+impl std::fmt::Display for ColoursError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} colours is too many for a GIF, it can only do 256",
+            self.0
+        )
+    }
+}
+
+// Every cell as a `factor` x `factor` square, so it's big enough to see.
+pub fn scale(image: &Grid<Colour>, factor: usize) -> Grid<Colour> {
+    let mut scaled = Grid::new(
+        image.width() * factor,
+        image.height() * factor,
+        Colour::BLACK,
+    );
+
+    for (x, y) in scaled.positions().collect::<Vec<(usize, usize)>>() {
+        scaled[(x, y)] = image[(x / factor, y / factor)];
+    }
+
+    scaled
+}
+
+// Put every frame in the middle of one the size of the biggest, so they can
+// go in the same animation.  The space around a frame is the colour of its
+// top left corner, which is usually the background.
+pub fn fit(frames: &[Grid<Colour>]) -> Vec<Grid<Colour>> {
+    let width = frames.iter().map(|frame| frame.width()).max().unwrap_or(0);
+    let height = frames.iter().map(|frame| frame.height()).max().unwrap_or(0);
+
+    frames
+        .iter()
+        .map(|frame| {
+            let mut fitted = Grid::new(width, height, frame[(0, 0)]);
+            let (dx, dy) = ((width - frame.width()) / 2, (height - frame.height()) / 2);
+            for (x, y) in frame.positions() {
+                fitted[(x + dx, y + dy)] = frame[(x, y)];
+            }
+            fitted
+        })
+        .collect()
+}
+
+// Both formats pack codes into bytes starting from the least significant bit.
+struct Bits {
+    bytes: Vec<u8>,
+    acc: u32,
+    count: u32,
+}
+
+impl Bits {
+    fn new() -> Bits {
+        Bits {
+            bytes: vec![],
+            acc: 0,
+            count: 0,
+        }
+    }
+
+    fn write(&mut self, value: u32, bits: u32) {
+        self.acc |= value << self.count;
+        self.count += bits;
+        while self.count >= 8 {
+            self.bytes.push(self.acc as u8);
+            self.acc >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    // Huffman codes go in the other way round, most significant bit first.
+    fn write_code(&mut self, code: u32, bits: u32) {
+        let reversed = (0..bits).fold(0, |acc, n| acc << 1 | (code >> n & 1));
+        self.write(reversed, bits);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.bytes.push(self.acc as u8);
+        }
+        self.bytes
+    }
+}
+
+const LENGTH_BASE: [usize; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u32; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASE: [usize; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [u32; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+// The fixed Huffman code for a literal byte, a length, or the end of the block.
+fn write_symbol(bits: &mut Bits, symbol: u32) {
+    match symbol {
+        0..=143 => bits.write_code(0x30 + symbol, 8),
+        144..=255 => bits.write_code(0x190 + symbol - 144, 9),
+        256..=279 => bits.write_code(symbol - 256, 7),
+        _ => bits.write_code(0xc0 + symbol - 280, 8),
+    }
+}
+
+// Look up which code a length or distance falls under.
+fn bucket(bases: &[usize], value: usize) -> usize {
+    bases.iter().rposition(|&base| base <= value).unwrap()
+}
+
+const WINDOW: usize = 32768;
+const MAX_MATCH: usize = 258;
+// How far back down each hash chain to look for a longer match.
+const CHAIN: usize = 32;
+
+// DEFLATE with the fixed Huffman codes.  The pictures are mostly the same
+// few colours over and over, so finding repeats is what matters, and building
+// Huffman tables wouldn't buy much.
+fn deflate(data: &[u8]) -> Vec<u8> {
+    let mut bits = Bits::new();
+    // the last block, with fixed codes
+    bits.write(1, 1);
+    bits.write(1, 2);
+
+    let hash = |n: usize| {
+        ((data[n] as usize) << 10 ^ (data[n + 1] as usize) << 5 ^ data[n + 2] as usize) & 0x7fff
+    };
+    // Where each hash was last seen, and where it was seen before that.
+    let mut head = vec![usize::MAX; 0x8000];
+    let mut previous = vec![usize::MAX; data.len()];
+    let insert = |n: usize, head: &mut Vec<usize>, previous: &mut Vec<usize>| {
+        if n + 2 < data.len() {
+            let h = hash(n);
+            previous[n] = head[h];
+            head[h] = n;
+        }
+    };
+
+    let mut n = 0;
+    while n < data.len() {
+        let (mut best, mut distance) = (0, 0);
+
+        if n + 2 < data.len() {
+            let mut candidate = head[hash(n)];
+            for _ in 0..CHAIN {
+                if candidate == usize::MAX || n - candidate > WINDOW {
+                    break;
+                }
+                let length = (0..MAX_MATCH.min(data.len() - n))
+                    .take_while(|&i| data[candidate + i] == data[n + i])
+                    .count();
+                if length > best {
+                    (best, distance) = (length, n - candidate);
+                }
+                candidate = previous[candidate];
+            }
+        }
+
+        if best >= 3 {
+            let code = bucket(&LENGTH_BASE, best);
+            write_symbol(&mut bits, 257 + code as u32);
+            bits.write((best - LENGTH_BASE[code]) as u32, LENGTH_EXTRA[code]);
+            let code = bucket(&DISTANCE_BASE, distance);
+            bits.write_code(code as u32, 5);
+            bits.write(
+                (distance - DISTANCE_BASE[code]) as u32,
+                DISTANCE_EXTRA[code],
+            );
+
+            for i in n..n + best {
+                insert(i, &mut head, &mut previous);
+            }
+            n += best;
+        } else {
+            write_symbol(&mut bits, data[n] as u32);
+            insert(n, &mut head, &mut previous);
+            n += 1;
+        }
+    }
+    write_symbol(&mut bits, 256);
+
+    bits.finish()
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut table = [0u32; 256];
+    for (n, entry) in table.iter_mut().enumerate() {
+        *entry = (0..8).fold(n as u32, |c, _| {
+            if c & 1 == 1 {
+                0xedb8_8320 ^ (c >> 1)
+            } else {
+                c >> 1
+            }
+        });
+    }
+
+    !bytes.iter().fold(!0u32, |crc, &b| {
+        table[((crc ^ b as u32) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (a, b) = bytes.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + byte as u32) % 65521;
+        (a, (b + a) % 65521)
+    });
+
+    b << 16 | a
+}
+
+fn chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+pub fn png(image: &Grid<Colour>) -> Vec<u8> {
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+
+    let mut header = vec![];
+    header.extend((image.width() as u32).to_be_bytes());
+    header.extend((image.height() as u32).to_be_bytes());
+    // 8 bits per channel, RGB, and the only compression, filtering and
+    // (no) interlacing there are
+    header.extend([8, 2, 0, 0, 0]);
+    chunk(&mut png, b"IHDR", &header);
+
+    // Each row starts with its filter, and we don't filter.
+    let raw: Vec<u8> = image
+        .rows()
+        .flat_map(|row| std::iter::once(0).chain(row.iter().flat_map(|&Colour(r, g, b)| [r, g, b])))
+        .collect();
+    // A zlib stream: header, DEFLATE data, and a checksum of what went in.
+    let mut data = vec![0x78, 0x01];
+    data.extend(deflate(&raw));
+    data.extend(adler32(&raw).to_be_bytes());
+    chunk(&mut png, b"IDAT", &data);
+
+    chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+// GIF's LZW, growing the codes a bit at a time up to 12 bits and starting
+// over once they're used up.
+fn lzw(indices: &[u8], min_size: u32) -> Vec<u8> {
+    let clear = 1u32 << min_size;
+    let end = clear + 1;
+    let mut bits = Bits::new();
+    let mut size = min_size + 1;
+    let mut next = end + 1;
+    let mut codes: HashMap<(u32, u8), u32> = HashMap::new();
+
+    bits.write(clear, size);
+    let mut prefix = indices[0] as u32;
+
+    for &index in &indices[1..] {
+        if let Some(&code) = codes.get(&(prefix, index)) {
+            prefix = code;
+            continue;
+        }
+
+        bits.write(prefix, size);
+        if next >= 1 << size && size < 12 {
+            size += 1;
+        }
+        if next >= 4095 {
+            bits.write(clear, size);
+            codes.clear();
+            size = min_size + 1;
+            next = end + 1;
+        } else {
+            codes.insert((prefix, index), next);
+            next += 1;
+        }
+        prefix = index as u32;
+    }
+
+    bits.write(prefix, size);
+    if next >= 1 << size && size < 12 {
+        size += 1;
+    }
+    bits.write(end, size);
+
+    bits.finish()
+}
+
+// An animation of the frames, which all have to be the same size (see fit),
+// looping forever with `delay` hundredths of a second between frames.
+pub fn gif(frames: &[Grid<Colour>], delay: u16) -> Result<Vec<u8>, ColoursError> {
+    let mut palette: Vec<Colour> = vec![];
+    let mut lookup: HashMap<Colour, u8> = HashMap::new();
+    for colour in frames.iter().flat_map(|frame| frame.iter()) {
+        if !lookup.contains_key(colour) {
+            lookup.insert(*colour, palette.len() as u8);
+            palette.push(*colour);
+        }
+        if palette.len() > 256 {
+            return Err(ColoursError(
+                frames
+                    .iter()
+                    .flat_map(|frame| frame.iter())
+                    .collect::<std::collections::HashSet<&Colour>>()
+                    .len(),
+            ));
+        }
+    }
+    // The colour table is a power of two long, at least 4.
+    let table_bits = (2..=8).find(|&bits| palette.len() <= 1 << bits).unwrap();
+
+    let (width, height) = frames.first().map_or((0, 0), |frame| {
+        (frame.width() as u16, frame.height() as u16)
+    });
+    let mut gif = b"GIF89a".to_vec();
+    gif.extend(width.to_le_bytes());
+    gif.extend(height.to_le_bytes());
+    // a global colour table, 8 bits per channel
+    gif.extend([0xf0 | (table_bits - 1) as u8, 0, 0]);
+    for n in 0..1 << table_bits {
+        let Colour(r, g, b) = palette.get(n).copied().unwrap_or(Colour::BLACK);
+        gif.extend([r, g, b]);
+    }
+    // Loop forever.
+    gif.extend(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00");
+
+    for frame in frames {
+        gif.extend([0x21, 0xf9, 0x04, 0x00]);
+        gif.extend(delay.to_le_bytes());
+        gif.extend([0x00, 0x00]);
+
+        gif.push(0x2c);
+        for n in [0, 0, frame.width() as u16, frame.height() as u16] {
+            gif.extend(n.to_le_bytes());
+        }
+        gif.push(0x00);
+
+        gif.push(table_bits as u8);
+        let indices: Vec<u8> = frame.iter().map(|colour| lookup[colour]).collect();
+        for block in lzw(&indices, table_bits).chunks(255) {
+            gif.push(block.len() as u8);
+            gif.extend(block);
+        }
+        gif.push(0x00);
+    }
+
+    gif.push(0x3b);
+    Ok(gif)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn checksum_test() {
+        assert!(crc32(b"IEND") == 0xae42_6082);
+        assert!(crc32(b"123456789") == 0xcbf4_3926);
+        assert!(adler32(b"Wikipedia") == 0x11e6_0398);
+    }
+
+    #[test]
+    fn png_test() {
+        let image = Grid::new(3, 2, Colour(1, 2, 3));
+        let png = png(&image);
+
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR\x00\x00\x00\x03"));
+        assert!(png.ends_with(b"\x00\x00\x00\x00IEND\xae\x42\x60\x82"));
+    }
+
+    #[test]
+    fn deflate_test() {
+        // A byte, then 257 copies of it from one back: a literal then a
+        // single length/distance pair, so the stream is only a few bytes.
+        let data = vec![7u8; 258];
+        assert!(deflate(&data).len() < 8);
+    }
+
+    #[test]
+    fn gif_test() {
+        let frames = [
+            Grid::new(2, 2, Colour::BLACK),
+            Grid::new(2, 2, Colour::WHITE),
+        ];
+        let gif = gif(&frames, 10).unwrap();
+
+        assert!(gif.starts_with(b"GIF89a\x02\x00\x02\x00\xf1"));
+        assert!(gif.ends_with(b"\x00\x3b"));
+
+        let many: Vec<Grid<Colour>> = (0..300)
+            .map(|n| Grid::new(1, 1, Colour(n as u8, (n / 256) as u8, 0)))
+            .collect();
+        assert!(super::gif(&many, 10) == Err(ColoursError(300)));
+    }
+
+    #[test]
+    fn fit_test() {
+        let small = Grid::new(1, 1, Colour::WHITE);
+        let big = Grid::new(3, 5, Colour::BLACK);
+        let fitted = fit(&[small, big]);
+
+        assert!(fitted[0].width() == 3 && fitted[0].height() == 5);
+        assert!(fitted[0].iter().filter(|&&c| c == Colour::WHITE).count() == 15);
+        assert!(scale(&fitted[1], 2).width() == 6);
+    }
+}
@@ -0,0 +1,153 @@
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+// Point this at a directory of `day1`, `day2`, ... files to run on someone
+// else's inputs without touching the checkout.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+#[derive(Debug, PartialEq)]
+pub enum Source {
+    Path(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    pub fn read_lines(&self) -> Result<Vec<String>, InputError> {
+        match self {
+            Source::Path(path) => {
+                let input = File::open(path).map_err(|e| InputError::Read(self.to_string(), e))?;
+                BufReader::new(input)
+                    .lines()
+                    .collect::<Result<Vec<String>, std::io::Error>>()
+                    .map_err(|e| InputError::Read(self.to_string(), e))
+            }
+            Source::Stdin => std::io::stdin()
+                .lock()
+                .lines()
+                .collect::<Result<Vec<String>, std::io::Error>>()
+                .map_err(|e| InputError::Read(self.to_string(), e)),
+        }
+    }
+}
+
+// `-` is stdin, like every other command line tool.
+impl From<&str> for Source {
+    fn from(arg: &str) -> Self {
+        match arg {
+            "-" => Source::Stdin,
+            _ => Source::Path(PathBuf::from(arg)),
+        }
+    }
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Source::Path(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    NotFound(usize, PathBuf),
+    Read(String, std::io::Error),
+}
+
+impl std::error::Error for InputError {}
+
+impl std::fmt::Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            InputError::NotFound(day, path) => write!(
+                f,
+                "no input for day {} at {} (pass a path, `-` for stdin, or set {})",
+                day,
+                path.display(),
+                INPUT_DIR_VAR
+            ),
+            InputError::Read(source, e) => write!(f, "couldn't read {}: {}", source, e),
+        }
+    }
+}
+
+// Work out where a day's input comes from, in order of preference:
+//
+// 1. whatever was given on the command line
+// 2. `$AOC_INPUT_DIR/dayN`
+// 3. `input` next to the day's Cargo.toml, which is how it's always been
+//
+// If the environment variable is set we never fall back to the checkout.
+// Quietly answering with a different account's input is worse than an error.
+pub fn locate(
+    day: usize,
+    explicit: Option<Source>,
+    manifest_dir: &str,
+) -> Result<Source, InputError> {
+    locate_with(day, explicit, std::env::var_os(INPUT_DIR_VAR), manifest_dir)
+}
+
+fn locate_with(
+    day: usize,
+    explicit: Option<Source>,
+    input_dir: Option<OsString>,
+    manifest_dir: &str,
+) -> Result<Source, InputError> {
+    if let Some(source) = explicit {
+        return Ok(source);
+    }
+
+    let path = match input_dir {
+        Some(dir) => day_path(Path::new(&dir), day),
+        None => Path::new(manifest_dir).join("input"),
+    };
+
+    if path.is_file() {
+        Ok(Source::Path(path))
+    } else {
+        Err(InputError::NotFound(day, path))
+    }
+}
+
+// How a day's input is named inside an input directory.
+pub fn day_path(dir: &Path, day: usize) -> PathBuf {
+    dir.join(format!("day{}", day))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn from_arg_test() {
+        assert!(Source::from("-") == Source::Stdin);
+        assert!(Source::from("day7/input") == Source::Path(PathBuf::from("day7/input")));
+    }
+
+    #[test]
+    fn locate_test() {
+        let dir = env!("CARGO_MANIFEST_DIR");
+
+        // An explicit source wins even if it doesn't exist, so reading it gives a clear error.
+        let explicit = locate_with(7, Some(Source::from("nope")), None, dir).unwrap();
+        assert!(explicit == Source::Path(PathBuf::from("nope")));
+
+        // Nobody keeps an input next to common's Cargo.toml.
+        assert!(matches!(
+            locate_with(7, None, None, dir),
+            Err(InputError::NotFound(7, _))
+        ));
+        let src = locate_with(1, None, Some(OsString::from(dir)), "/nowhere");
+        assert!(matches!(src, Err(InputError::NotFound(1, p)) if p.ends_with("day1")));
+    }
+
+    #[test]
+    fn read_missing_test() {
+        let err = Source::from("/nowhere/day1").read_lines().unwrap_err();
+
+        assert!(err.to_string().starts_with("couldn't read /nowhere/day1: "));
+    }
+}
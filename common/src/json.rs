@@ -0,0 +1,72 @@
+// Just enough JSON to write our own output, so we don't have to pull in serde.
+
+pub fn string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+// Integers go out as numbers so they can be graphed, anything else as a string.
+pub fn value(s: &str) -> String {
+    match s.parse::<i64>() {
+        Ok(n) if n.to_string() == s => s.to_string(),
+        _ => string(s),
+    }
+}
+
+// Fields are written in the order given, values must already be JSON.
+pub fn object(fields: &[(&str, String)]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|(key, value)| format!("{}:{}", string(key), value))
+        .collect();
+    format!("{{{}}}", fields.join(","))
+}
+
+// One element per line, which keeps big arrays diffable.
+pub fn array(values: &[String]) -> String {
+    if values.is_empty() {
+        return String::from("[]");
+    }
+    format!("[\n  {}\n]", values.join(",\n  "))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn string_test() {
+        assert!(string("plain") == "\"plain\"");
+        assert!(string("a \"b\"\n\\") == "\"a \\\"b\\\"\\n\\\\\"");
+        assert!(string("\u{1}") == "\"\\u0001\"");
+    }
+
+    #[test]
+    fn value_test() {
+        assert!(value("-42") == "-42");
+        assert!(value("007") == "\"007\"");
+        assert!(value("1.5") == "\"1.5\"");
+        assert!(value("99999999999999999999") == "\"99999999999999999999\"");
+    }
+
+    #[test]
+    fn object_test() {
+        let obj = object(&[("day", 7.to_string()), ("answer", string("37"))]);
+
+        assert!(obj == "{\"day\":7,\"answer\":\"37\"}");
+        assert!(array(&[obj.clone(), obj.clone()]) == format!("[\n  {},\n  {}\n]", obj, obj));
+        assert!(array(&[]) == "[]");
+    }
+}
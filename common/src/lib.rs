@@ -0,0 +1,284 @@
+use std::fmt::Display;
+
+pub mod args;
+pub mod bench;
+pub mod differential;
+pub mod example;
+pub mod generate;
+pub mod grid;
+pub mod image;
+pub mod input;
+pub mod json;
+pub mod parse;
+pub mod progress;
+pub mod report;
+pub mod rng;
+pub mod table;
+pub mod visualise;
+
+pub use args::{Args, Subcommand};
+pub use example::Example;
+pub use grid::Grid;
+pub use input::Source;
+pub use parse::Located;
+pub use progress::Progress;
+pub use report::{note, note_with};
+pub use rng::Rng;
+pub use visualise::{Simulation, Visualise};
+
+// Every day has the same shape: parse the puzzle input once, then answer both
+// parts from what was parsed.  Tooling only ever talks to a day through this.
+pub trait Solution {
+    const DAY: usize;
+    // Where the day's Cargo.toml is, since its input lives next to it by default.
+    const MANIFEST_DIR: &'static str;
+    // The examples from the puzzle text, checked by `aoc example` and the
+    // day's tests.
+    const EXAMPLES: &'static [Example];
+
+    type Input;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input, Box<dyn std::error::Error>>;
+    fn part1(input: &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>>;
+    fn part2(input: &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>>;
+
+    // A random input that parses, for stress tests.  What `size` counts
+    // (lines, grid width, scanners...) is up to the day, and None is about as
+    // big as the real puzzle input.
+    fn generate(rng: &mut Rng, size: Option<usize>) -> Vec<String>;
+}
+
+// For days that keep a slow but obviously right way of solving the puzzle
+// around, so the fast way in `part1` and `part2` can be checked against it.
+pub trait Reference: Solution {
+    fn reference1(input: &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>>;
+    fn reference2(input: &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>>;
+}
+
+// Tests every day gets without having to write them out 25 times: that it
+// solves the examples from the puzzle, and can parse what it generates.
+#[macro_export]
+macro_rules! solution_tests {
+    ($solution:ty) => {
+        #[cfg(test)]
+        mod solution {
+            use super::*;
+
+            #[test]
+            fn examples_test() {
+                $crate::example::assert_examples::<$solution>();
+            }
+
+            #[test]
+            fn generated_test() {
+                $crate::generate::assert_generated::<$solution>();
+            }
+        }
+    };
+}
+
+// Check a day's fast and reference solutions agree on generated inputs of
+// each of the given sizes.  Keep the sizes small: the reference is slow.
+#[macro_export]
+macro_rules! reference_tests {
+    ($solution:ty, $sizes:expr) => {
+        #[cfg(test)]
+        mod reference {
+            use super::*;
+
+            #[test]
+            fn reference_test() {
+                $crate::differential::assert_agree::<$solution>(&$sizes);
+            }
+        }
+    };
+}
+
+// Find the day's input (see input::locate) and read it in.
+pub fn load<S: Solution>(source: Option<Source>) -> Result<Vec<String>, input::InputError> {
+    input::locate(S::DAY, source, S::MANIFEST_DIR)?.read_lines()
+}
+
+pub fn run<S: Solution>(source: Option<Source>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    solve::<S>(load::<S>(source)?)
+}
+
+// Parse and solve both parts, rendering the answers for display.
+pub fn solve<S: Solution>(lines: Vec<String>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let report = report::report::<S>(lines)?;
+
+    Ok(report.parts.into_iter().map(|part| part.answer).collect())
+}
+
+// All a day's binary does unless it has commands of its own: answer both
+// parts for the input named on the command line.
+pub fn main<S: Solution>() {
+    let usage = format!(
+        "usage: day{} [<path>]\n\n<path> is the puzzle input, or - for stdin",
+        S::DAY
+    );
+    let args = args::parse::<S>(&[], &usage);
+
+    exit_on_error::<S>(answer::<S>(args.path()))
+}
+
+// An explicit path, or `-` for stdin.  Otherwise see input::locate.
+pub fn answer<S: Solution>(path: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    for (part, answer) in run::<S>(path.map(Source::from))?.iter().enumerate() {
+        println!("Part {}: {}", part + 1, answer);
+    }
+    Ok(())
+}
+
+// Returning the error from main() would print its Debug form, which isn't
+// much use here.
+pub fn exit_on_error<S: Solution>(result: Result<(), Box<dyn std::error::Error>>) {
+    if let Err(e) = result {
+        eprintln!("day{}: {}", S::DAY, e);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Doubler;
+
+    impl Solution for Doubler {
+        const DAY: usize = 0;
+        const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+        const EXAMPLES: &'static [Example] = &[
+            Example {
+                input: "1\n2",
+                part1: Some("6"),
+                part2: None,
+            },
+            Example {
+                input: "3",
+                part1: Some("7"),
+                part2: Some("[3]"),
+            },
+            Example {
+                input: "three",
+                part1: None,
+                part2: Some("[3]"),
+            },
+        ];
+
+        type Input = Vec<u64>;
+        type Part1 = u64;
+        type Part2 = String;
+
+        fn parse(lines: Vec<String>) -> Result<Self::Input, Box<dyn std::error::Error>> {
+            Ok(lines
+                .iter()
+                .map(|l| l.parse::<u64>())
+                .collect::<Result<Vec<u64>, std::num::ParseIntError>>()?)
+        }
+
+        fn part1(input: &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+            note("count", input.len());
+            Ok(input.iter().sum::<u64>() * 2)
+        }
+
+        fn part2(input: &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+            Ok(format!("{:?}", input))
+        }
+
+        fn generate(rng: &mut Rng, size: Option<usize>) -> Vec<String> {
+            (0..size.unwrap_or(10))
+                .map(|_| rng.below(100).to_string())
+                .collect()
+        }
+    }
+
+    impl Reference for Doubler {
+        fn reference1(input: &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+            Ok(input.iter().map(|n| n + n).sum())
+        }
+
+        // Wrong once there's more than a couple of numbers.
+        fn reference2(input: &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+            Ok(format!("{:?}", &input[..input.len().min(2)]))
+        }
+    }
+
+    #[test]
+    fn solve_test() {
+        let answers = solve::<Doubler>(vec!["1".to_string(), "2".to_string()]).unwrap();
+
+        assert!(answers == vec!["6".to_string(), "[1, 2]".to_string()]);
+    }
+
+    #[test]
+    fn bench_test() {
+        let report = bench::bench::<Doubler>(&["1".to_string()], 3).unwrap();
+
+        assert!(report.runs == 3);
+        assert!(report.part1.min <= report.part1.median);
+        assert!(report.part1.median <= report.part1.max);
+        assert!(bench::bench::<Doubler>(&["one".to_string()], 3).is_err());
+    }
+
+    #[test]
+    fn report_test() {
+        let report = report::report::<Doubler>(vec!["1".to_string(), "2".to_string()]).unwrap();
+
+        assert!(report.parts.len() == 2);
+        assert!(report.parts[0].answer == "6");
+        assert!(report.parts[0].diagnostics == vec![("count".to_string(), "2".to_string())]);
+        assert!(report.parts[1].diagnostics.is_empty());
+        assert!(report::take_notes().is_empty());
+    }
+
+    #[test]
+    fn lazy_note_test() {
+        // Nobody's listening outside a report, so neither is kept.
+        report::note_with("never", || -> String {
+            panic!("worked out an unwanted note")
+        });
+        report::note("count", 2);
+        assert!(report::take_notes().is_empty());
+    }
+
+    #[test]
+    fn example_test() {
+        let checked = example::check::<Doubler>();
+
+        assert!(checked.len() == 4);
+        assert!(checked[0].passed());
+        assert!((checked[1].example, checked[1].part) == (2, 1));
+        assert!(checked[1].answer == Ok("6".to_string()) && !checked[1].passed());
+        assert!(checked[2].passed());
+        assert!(checked[3].answer.is_err());
+        assert!(report::take_notes().is_empty());
+    }
+
+    #[test]
+    fn generate_test() {
+        let lines = Doubler::generate(&mut Rng::new(3), Some(4));
+
+        assert!(lines.len() == 4);
+        assert!(lines == Doubler::generate(&mut Rng::new(3), Some(4)));
+        assert!(Doubler::parse(lines).is_ok());
+    }
+
+    #[test]
+    fn differential_test() {
+        assert!(differential::differ::<Doubler>(5, &[1, 2]).is_none());
+
+        let disagreement = differential::differ::<Doubler>(5, &[1, 2, 3, 4]).unwrap();
+        assert!((disagreement.seed, disagreement.size, disagreement.part) == (0, 3, 2));
+        assert!(disagreement.reference != disagreement.fast);
+        assert!(disagreement.input.len() == 3);
+        assert!(report::take_notes().is_empty());
+    }
+
+    #[test]
+    fn solve_parse_error_test() {
+        assert!(solve::<Doubler>(vec!["one".to_string()]).is_err());
+    }
+}
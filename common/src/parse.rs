@@ -0,0 +1,66 @@
+use std::str::FromStr;
+
+// Something that went wrong parsing the input, and which line (counting from
+// 1) it was on, so a bad input can be fixed without a debugger.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Located<E> {
+    pub line: usize,
+    pub error: E,
+}
+
+impl<E> Located<E> {
+    pub fn new(line: usize, error: E) -> Located<E> {
+        Located { line, error }
+    }
+}
+
+impl<E: std::error::Error> std::error::Error for Located<E> {}
+
+impl<E: std::fmt::Display> std::fmt::Display for Located<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.error)
+    }
+}
+
+// Parse every line as a T.  `first` is the line number of lines[0], for when
+// we're only looking at part of the input.
+pub fn lines<T: FromStr>(lines: &[String], first: usize) -> Result<Vec<T>, Located<T::Err>> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(n, line)| line.parse::<T>().map_err(|e| Located::new(first + n, e)))
+        .collect()
+}
+
+// Where a piece of a line starts, counting from 1.  The piece has to be a
+// slice of the line, from split() or similar, rather than a copy of it.
+pub fn column(line: &str, piece: &str) -> usize {
+    let offset = piece.as_ptr() as usize - line.as_ptr() as usize;
+    assert!(offset <= line.len(), "piece isn't part of the line");
+
+    line[..offset].chars().count() + 1
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn lines_test() {
+        let good = vec!["1".to_string(), "2".to_string()];
+        let bad = vec!["1".to_string(), "x".to_string()];
+
+        assert!(lines::<u8>(&good, 1) == Ok(vec![1, 2]));
+        assert!(lines::<u8>(&bad, 3).unwrap_err().line == 4);
+    }
+
+    #[test]
+    fn column_test() {
+        let line = "move 12 -> 4";
+        let pieces: Vec<&str> = line.split(' ').collect();
+
+        assert!(column(line, pieces[0]) == 1);
+        assert!(column(line, pieces[1]) == 6);
+        assert!(column(line, pieces[3]) == 12);
+    }
+}
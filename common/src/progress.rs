@@ -0,0 +1,243 @@
+use std::cell::{Cell, RefCell};
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+// How often a search tells the hook how it's getting on.
+const REPORT_EVERY: Duration = Duration::from_millis(500);
+
+// How far a long search is allowed to go before giving up.  Each search gets
+// the whole budget to itself, so part 1 and part 2 get one each.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Budget {
+    pub time: Option<Duration>,
+    pub steps: Option<u64>,
+}
+
+// Where a search has got to, for whoever's watching.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Update {
+    pub what: &'static str,
+    pub unit: &'static str,
+    pub steps: u64,
+    // how many steps there are in all, if the search knows
+    pub total: Option<u64>,
+    pub elapsed: Duration,
+}
+
+impl Display for Update {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}: {}", self.what, self.steps)?;
+        if let Some(total) = self.total {
+            write!(f, "/{}", total)?;
+        }
+        write!(f, " {} in {:.1?}", self.unit, self.elapsed)
+    }
+}
+
+type Hook = Box<dyn FnMut(&Update)>;
+
+thread_local! {
+    static BUDGET: Cell<Budget> = const { Cell::new(Budget { time: None, steps: None }) };
+    static HOOK: RefCell<Option<Hook>> = const { RefCell::new(None) };
+}
+
+// The budget for every search started on this thread from now on.  It's per
+// thread, like notes, so the runner can give each day its own.
+pub fn set_budget(budget: Budget) {
+    BUDGET.with(|b| b.set(budget));
+}
+
+// Have searches on this thread report how they're getting on every so often.
+pub fn set_hook(hook: impl FnMut(&Update) + 'static) {
+    HOOK.with(|h| *h.borrow_mut() = Some(Box::new(hook)));
+}
+
+pub fn clear_hook() {
+    HOOK.with(|h| *h.borrow_mut() = None);
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Limit {
+    Time(Duration),
+    Steps(u64),
+}
+
+// A search that used up its budget, and how far it got.
+#[derive(Debug, PartialEq)]
+pub struct OutOfBudget {
+    pub update: Update,
+    pub limit: Limit,
+}
+
+impl std::error::Error for OutOfBudget {}
+
+impl Display for OutOfBudget {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "gave up {} after ", self.update.what)?;
+        match self.limit {
+            Limit::Time(time) => {
+                write!(f, "{:.1?}, with {}", time, self.update.steps)?;
+                if let Some(total) = self.update.total {
+                    write!(f, "/{}", total)?;
+                }
+                write!(f, " {}", self.update.unit)
+            }
+            Limit::Steps(steps) => write!(
+                f,
+                "{} {}, in {:.1?}",
+                steps, self.update.unit, self.update.elapsed
+            ),
+        }
+    }
+}
+
+// Something a long search keeps to report into as it goes: it counts steps
+// against the thread's budget and passes updates on to the hook.
+pub struct Progress {
+    what: &'static str,
+    unit: &'static str,
+    steps: u64,
+    total: Option<u64>,
+    budget: Budget,
+    start: Instant,
+    reported: Instant,
+}
+
+impl Progress {
+    // `what` is the search ("aligning scanners"), `unit` is what a step is
+    // ("scanners merged").
+    pub fn start(what: &'static str, unit: &'static str) -> Progress {
+        let now = Instant::now();
+
+        Progress {
+            what,
+            unit,
+            steps: 0,
+            total: None,
+            budget: BUDGET.with(|b| b.get()),
+            start: now,
+            reported: now,
+        }
+    }
+
+    pub fn with_total(mut self, total: u64) -> Progress {
+        self.total = Some(total);
+        self
+    }
+
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    pub fn update(&self) -> Update {
+        Update {
+            what: self.what,
+            unit: self.unit,
+            steps: self.steps,
+            total: self.total,
+            elapsed: self.start.elapsed(),
+        }
+    }
+
+    pub fn step(&mut self) -> Result<(), OutOfBudget> {
+        self.advance(1)
+    }
+
+    pub fn advance(&mut self, steps: u64) -> Result<(), OutOfBudget> {
+        self.steps += steps;
+
+        if let Some(limit) = self.budget.steps {
+            if self.steps > limit {
+                return Err(OutOfBudget {
+                    update: self.update(),
+                    limit: Limit::Steps(limit),
+                });
+            }
+        }
+        self.check()
+    }
+
+    // Check the clock without counting a step, for work between steps that
+    // can take a while by itself.
+    pub fn check(&mut self) -> Result<(), OutOfBudget> {
+        let now = Instant::now();
+
+        if let Some(limit) = self.budget.time {
+            if now - self.start > limit {
+                return Err(OutOfBudget {
+                    update: self.update(),
+                    limit: Limit::Time(limit),
+                });
+            }
+        }
+        if now - self.reported >= REPORT_EVERY {
+            self.reported = now;
+            let update = self.update();
+            HOOK.with(|hook| {
+                if let Some(hook) = hook.borrow_mut().as_mut() {
+                    hook(&update);
+                }
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn steps_test() {
+        set_budget(Budget {
+            time: None,
+            steps: Some(3),
+        });
+        let mut progress = Progress::start("counting", "sheep").with_total(10);
+
+        assert!((0..3).all(|_| progress.step().is_ok()));
+        let e = progress.step().unwrap_err();
+        assert!(e.limit == Limit::Steps(3));
+        assert!(e.update.steps == 4 && e.update.total == Some(10));
+        assert!(e
+            .to_string()
+            .starts_with("gave up counting after 3 sheep, in "));
+
+        set_budget(Budget::default());
+        let mut progress = Progress::start("counting", "sheep");
+        assert!(progress.advance(1000).is_ok());
+    }
+
+    #[test]
+    fn time_test() {
+        set_budget(Budget {
+            time: Some(Duration::from_millis(10)),
+            steps: None,
+        });
+        let mut progress = Progress::start("waiting", "naps");
+
+        assert!(progress.check().is_ok());
+        std::thread::sleep(Duration::from_millis(20));
+        let e = progress.check().unwrap_err();
+        assert!(e.limit == Limit::Time(Duration::from_millis(10)));
+        assert!(e.to_string() == "gave up waiting after 10.0ms, with 0 naps");
+        set_budget(Budget::default());
+    }
+
+    #[test]
+    fn hook_test() {
+        let seen = std::rc::Rc::new(RefCell::new(vec![]));
+        let log = seen.clone();
+        set_hook(move |update| log.borrow_mut().push(update.steps));
+
+        let mut progress = Progress::start("waiting", "naps");
+        progress.step().unwrap();
+        // Too soon to say anything.
+        assert!(seen.borrow().is_empty());
+        std::thread::sleep(REPORT_EVERY);
+        progress.step().unwrap();
+        assert!(*seen.borrow() == vec![2]);
+        clear_hook();
+    }
+}
@@ -0,0 +1,119 @@
+use crate::Solution;
+use std::cell::{Cell, RefCell};
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+// A note's key, and how to work out its value when someone asks for it.
+type Note = (String, Box<dyn FnOnce() -> String>);
+
+thread_local! {
+    static NOTES: RefCell<Vec<Note>> = const { RefCell::new(vec![]) };
+    static LISTENING: Cell<bool> = const { Cell::new(false) };
+}
+
+// Notes are only kept while a report is being made, so a day used as a
+// library or being benchmarked doesn't pile them up.  Put back how it was
+// when dropped.
+struct Listening(bool);
+
+impl Listening {
+    fn start() -> Listening {
+        Listening(LISTENING.replace(true))
+    }
+}
+
+impl Drop for Listening {
+    fn drop(&mut self) {
+        LISTENING.set(self.0);
+    }
+}
+
+// Record something interesting about how an answer was found, like how many
+// iterations it took.  It's attached to whichever part is running at the time,
+// and ignored when nobody is asking for a report.
+pub fn note(key: &str, value: impl Display) {
+    if LISTENING.get() {
+        let value = value.to_string();
+        note_with(key, move || value);
+    }
+}
+
+// A note that takes some working out, which is only done once the part has
+// finished and someone wants to see it, so it isn't timed with the part.
+pub fn note_with<T: Display>(key: &str, value: impl FnOnce() -> T + 'static) {
+    if !LISTENING.get() {
+        return;
+    }
+    NOTES.with(|notes| {
+        notes
+            .borrow_mut()
+            .push((key.to_string(), Box::new(move || value().to_string())))
+    });
+}
+
+// Everything noted on this thread since the last call.
+pub fn take_notes() -> Vec<(String, String)> {
+    let notes = NOTES.with(|notes| notes.take());
+
+    notes
+        .into_iter()
+        .map(|(key, value)| (key, value()))
+        .collect()
+}
+
+// Forget everything noted on this thread, without working any of it out.
+pub fn discard_notes() {
+    NOTES.with(|notes| notes.take());
+}
+
+#[derive(Debug, Clone)]
+pub struct PartReport {
+    pub answer: String,
+    pub time: Duration,
+    pub diagnostics: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DayReport {
+    pub parse_time: Duration,
+    pub parts: Vec<PartReport>,
+}
+
+// Parse and solve both parts once, keeping track of how long each step took
+// and anything the day noted along the way.
+pub fn report<S: Solution>(lines: Vec<String>) -> Result<DayReport, Box<dyn std::error::Error>> {
+    let _listening = Listening::start();
+    discard_notes();
+
+    let start = Instant::now();
+    let input = S::parse(lines);
+    let parse_time = start.elapsed();
+    // Anything noted while parsing isn't about either part.
+    discard_notes();
+    let input = input?;
+
+    let start = Instant::now();
+    let answer = S::part1(&input);
+    let time = start.elapsed();
+    let diagnostics = take_notes();
+    let part1 = PartReport {
+        answer: answer?.to_string(),
+        time,
+        diagnostics,
+    };
+
+    let start = Instant::now();
+    let answer = S::part2(&input);
+    let time = start.elapsed();
+    let diagnostics = take_notes();
+    let part2 = PartReport {
+        answer: answer?.to_string(),
+        time,
+        diagnostics,
+    };
+
+    Ok(DayReport {
+        parse_time,
+        parts: vec![part1, part2],
+    })
+}
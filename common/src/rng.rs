@@ -0,0 +1,100 @@
+use std::time::SystemTime;
+
+// Just enough randomness to generate puzzle inputs: SplitMix64, which is tiny,
+// fast and good enough for anything that isn't cryptography.  The same seed
+// always gives the same numbers, so a generated input can be made again.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    // A different seed every time, for when nobody asked for a particular
+    // one.  Say what it was, so the same numbers can be had again.
+    pub fn time_seed() -> u64 {
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |time| time.as_nanos() as u64)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // Somewhere in 0..n, which can't be empty.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "nothing below 0 to pick");
+
+        // Scaling rather than taking the remainder keeps it (near enough) uniform.
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    // Somewhere in min..=max.
+    pub fn between(&mut self, min: i64, max: i64) -> i64 {
+        assert!(min <= max, "{}..={} is empty", min, max);
+
+        min + self.below((max - min) as usize + 1) as i64
+    }
+
+    // True `n` times out of `out_of`.
+    pub fn chance(&mut self, n: usize, out_of: usize) -> bool {
+        self.below(out_of) < n
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for n in (1..items.len()).rev() {
+            items.swap(n, self.below(n + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn seed_test() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        let mut c = Rng::new(8);
+
+        let a: Vec<u64> = (0..5).map(|_| a.next_u64()).collect();
+        let b: Vec<u64> = (0..5).map(|_| b.next_u64()).collect();
+        let c: Vec<u64> = (0..5).map(|_| c.next_u64()).collect();
+
+        assert!(a == b);
+        assert!(a != c);
+    }
+
+    #[test]
+    fn range_test() {
+        let mut rng = Rng::new(1);
+        let mut seen = [false; 5];
+
+        for _ in 0..1000 {
+            let n = rng.between(-2, 2);
+            assert!((-2..=2).contains(&n));
+            seen[(n + 2) as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+        assert!((0..100).all(|_| rng.below(1) == 0));
+
+        let mut items: Vec<usize> = (0..20).collect();
+        rng.shuffle(&mut items);
+        items.sort();
+        assert!(items == (0..20).collect::<Vec<usize>>());
+    }
+}
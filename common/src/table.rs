@@ -0,0 +1,47 @@
+// Plain text tables, one row per thing: the runner's answers and timings, and
+// the days with reports of their own.
+pub fn print(header: &[&str], rows: &[Vec<String>]) {
+    let header: Vec<String> = header.iter().map(|h| h.to_string()).collect();
+
+    // Some answers (looking at you, day 13) span several lines.
+    let widths: Vec<usize> = (0..header.len())
+        .map(|col| {
+            rows.iter()
+                .chain([&header])
+                .filter_map(|row| row.get(col))
+                .flat_map(|cell| cell.lines())
+                .map(|line| line.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let print_row = |row: &Vec<String>| {
+        let height = row
+            .iter()
+            .map(|cell| cell.lines().count())
+            .max()
+            .unwrap_or(1);
+        for n in 0..height.max(1) {
+            let line = (0..widths.len())
+                .map(|col| {
+                    let cell = row.get(col).and_then(|c| c.lines().nth(n)).unwrap_or("");
+                    format!("{:width$}", cell, width = widths[col])
+                })
+                .collect::<Vec<String>>()
+                .join(" | ");
+            println!("{}", line.trim_end());
+        }
+    };
+
+    print_row(&header);
+    println!(
+        "{}",
+        widths
+            .iter()
+            .map(|w| "-".repeat(*w))
+            .collect::<Vec<String>>()
+            .join("-+-")
+    );
+    rows.iter().for_each(print_row);
+}
@@ -0,0 +1,179 @@
+use crate::image::Colour;
+use crate::{Grid, Solution};
+use std::str::FromStr;
+
+// A puzzle that plays out one step at a time, for watching it happen.
+pub trait Simulation {
+    // Move on a step, or false if there's nothing left to happen.
+    fn step(&mut self) -> bool;
+    // The state as it is now, as text for the terminal.  The first line is a
+    // caption saying what's going on.
+    fn render(&self) -> String;
+    // The state as a colour per cell, for saving as an image.  Not every
+    // simulation has one.
+    fn picture(&self) -> Option<Grid<Colour>> {
+        None
+    }
+}
+
+// For days whose solution is a simulation worth watching.
+pub trait Visualise: Solution {
+    fn simulation(input: &Self::Input) -> Box<dyn Simulation>;
+}
+
+// Steps through a simulation.  Simulations only go forwards, so every state
+// it's been through is kept to go back to.
+pub struct Player {
+    simulation: Box<dyn Simulation>,
+    frames: Vec<String>,
+    finished: bool,
+    current: usize,
+}
+
+impl Player {
+    pub fn new(simulation: Box<dyn Simulation>) -> Player {
+        let frames = vec![simulation.render()];
+
+        Player {
+            simulation,
+            frames,
+            finished: false,
+            current: 0,
+        }
+    }
+
+    // Which step we're looking at, 0 being the start.
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    pub fn frame(&self) -> &str {
+        &self.frames[self.current]
+    }
+
+    // The last step, once we've been that far.
+    pub fn last(&self) -> Option<usize> {
+        self.finished.then(|| self.frames.len() - 1)
+    }
+
+    pub fn at_end(&self) -> bool {
+        self.last() == Some(self.current)
+    }
+
+    // Go to step `n`, or as near as the simulation goes.  Returns where we
+    // ended up.
+    pub fn jump(&mut self, n: usize) -> usize {
+        while self.frames.len() <= n && !self.finished {
+            if self.simulation.step() {
+                self.frames.push(self.simulation.render());
+            } else {
+                self.finished = true;
+            }
+        }
+        self.current = n.min(self.frames.len() - 1);
+
+        self.current
+    }
+
+    // False if we're already at the end.
+    pub fn forward(&mut self) -> bool {
+        let from = self.current;
+
+        self.jump(from + 1) != from
+    }
+
+    // False if we're already at the start.
+    pub fn back(&mut self) -> bool {
+        let from = self.current;
+
+        self.jump(from.saturating_sub(1)) != from
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Forward,
+    Back,
+    // play if paused, pause if playing
+    Play,
+    Jump(usize),
+    Quit,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CommandError(pub String);
+
+impl std::error::Error for CommandError {}
+
+impl std::fmt::Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "\"{}\" isn't a command", self.0)
+    }
+}
+
+pub const COMMANDS: &str =
+    "enter/n: next step   b: back   p: play/pause   <n> or g <n>: go to step n   q: quit";
+
+impl FromStr for Command {
+    type Err = CommandError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        let step = |n: &str| n.parse::<usize>().ok().map(Command::Jump);
+
+        let command = match text {
+            "" | "n" => Some(Command::Forward),
+            "b" => Some(Command::Back),
+            "p" => Some(Command::Play),
+            "q" => Some(Command::Quit),
+            _ => match text.strip_prefix("g ") {
+                Some(n) => step(n.trim()),
+                None => step(text),
+            },
+        };
+
+        command.ok_or(CommandError(text.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Counts up to 3.
+    struct Counter(usize);
+
+    impl Simulation for Counter {
+        fn step(&mut self) -> bool {
+            self.0 += 1;
+            self.0 <= 3
+        }
+
+        fn render(&self) -> String {
+            self.0.to_string()
+        }
+    }
+
+    #[test]
+    fn player_test() {
+        let mut player = Player::new(Box::new(Counter(0)));
+
+        assert!(player.frame() == "0" && player.last().is_none());
+        assert!(!player.back());
+        assert!(player.forward() && player.frame() == "1");
+        assert!(player.jump(10) == 3 && player.frame() == "3");
+        assert!(player.last() == Some(3) && player.at_end());
+        assert!(!player.forward());
+        assert!(player.back() && player.frame() == "2");
+        assert!(player.jump(0) == 0 && player.frame() == "0");
+    }
+
+    #[test]
+    fn command_test() {
+        assert!("".parse::<Command>() == Ok(Command::Forward));
+        assert!(" b\n".parse::<Command>() == Ok(Command::Back));
+        assert!("g 12".parse::<Command>() == Ok(Command::Jump(12)));
+        assert!("7".parse::<Command>() == Ok(Command::Jump(7)));
+        assert!("go".parse::<Command>() == Err(CommandError("go".to_string())));
+    }
+}
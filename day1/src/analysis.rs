@@ -0,0 +1,354 @@
+use common::{json, table};
+use std::collections::VecDeque;
+use std::num::NonZeroUsize;
+
+// Line numbers are 1 based throughout, to match the report.
+
+// The longest stretch of readings where each was deeper than the last.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Run {
+    pub start: usize,
+    pub length: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fall {
+    // the line that came up shallower than the one before it
+    pub line: usize,
+    pub from: u64,
+    pub to: u64,
+}
+
+impl Fall {
+    pub fn size(&self) -> u64 {
+        self.from - self.to
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WindowStats {
+    pub start: usize,
+    pub min: u64,
+    pub max: u64,
+    // Wide enough for any window of u64s.
+    pub sum: u128,
+    pub size: usize,
+}
+
+impl WindowStats {
+    pub fn mean(&self) -> f64 {
+        self.sum as f64 / self.size as f64
+    }
+}
+
+// A reading too far from the average of the window before it, which is
+// usually the sonar having a funny moment rather than a trench.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Anomaly {
+    pub line: usize,
+    pub depth: u64,
+    pub expected: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Analysis {
+    pub readings: usize,
+    pub increases: usize,
+    pub decreases: usize,
+    pub unchanged: usize,
+    pub longest_rise: Run,
+    pub largest_drop: Option<Fall>,
+    pub windows: Vec<WindowStats>,
+    pub anomalies: Vec<Anomaly>,
+}
+
+// Everything about a report of depths, with windows `window` readings wide.
+// A reading is anomalous if it's more than `tolerance` away from the mean of
+// the window just before it.
+pub fn analyse(depths: &[u64], window: NonZeroUsize, tolerance: u64) -> Analysis {
+    let window = window.get();
+
+    let mut analysis = Analysis {
+        readings: depths.len(),
+        increases: 0,
+        decreases: 0,
+        unchanged: 0,
+        longest_rise: Run {
+            start: 1,
+            length: depths.len().min(1),
+        },
+        largest_drop: None,
+        windows: vec![],
+        anomalies: vec![],
+    };
+
+    let mut rise = analysis.longest_rise;
+    for (n, pair) in depths.windows(2).enumerate() {
+        let (from, to) = (pair[0], pair[1]);
+        let line = n + 2;
+
+        if to > from {
+            analysis.increases += 1;
+            rise.length += 1;
+        } else {
+            rise = Run {
+                start: line,
+                length: 1,
+            };
+            if to < from {
+                analysis.decreases += 1;
+                let fall = Fall { line, from, to };
+                if analysis.largest_drop.is_none_or(|d| fall.size() > d.size()) {
+                    analysis.largest_drop = Some(fall);
+                }
+            } else {
+                analysis.unchanged += 1;
+            }
+        }
+        if rise.length > analysis.longest_rise.length {
+            analysis.longest_rise = rise;
+        }
+    }
+
+    // Indexes of the depths that could still be the smallest (or largest) in
+    // a window, in order, so the front is always the answer for the current
+    // window.  Every depth goes in and out once, so it doesn't matter how big
+    // the window is.
+    let mut mins: VecDeque<usize> = VecDeque::new();
+    let mut maxes: VecDeque<usize> = VecDeque::new();
+    let mut sum: u128 = 0;
+
+    for (n, &depth) in depths.iter().enumerate() {
+        if n >= window {
+            // Compare in whole sums to stay in integers, and in u128s so
+            // that neither side can overflow.
+            let expected = sum as f64 / window as f64;
+            let size = window as u128;
+            if (depth as u128 * size).abs_diff(sum) > tolerance as u128 * size {
+                analysis.anomalies.push(Anomaly {
+                    line: n + 1,
+                    depth,
+                    expected,
+                });
+            }
+            sum -= depths[n - window] as u128;
+        }
+        sum += depth as u128;
+
+        while mins.back().is_some_and(|&m| depths[m] >= depth) {
+            mins.pop_back();
+        }
+        mins.push_back(n);
+        while maxes.back().is_some_and(|&m| depths[m] <= depth) {
+            maxes.pop_back();
+        }
+        maxes.push_back(n);
+
+        if n + 1 >= window {
+            let start = n + 1 - window;
+            while mins.front().is_some_and(|&m| m < start) {
+                mins.pop_front();
+            }
+            while maxes.front().is_some_and(|&m| m < start) {
+                maxes.pop_front();
+            }
+            analysis.windows.push(WindowStats {
+                start: start + 1,
+                min: depths[mins[0]],
+                max: depths[maxes[0]],
+                sum,
+                size: window,
+            });
+        }
+    }
+
+    analysis
+}
+
+impl Analysis {
+    pub fn to_json(&self) -> String {
+        let windows: Vec<String> = self
+            .windows
+            .iter()
+            .map(|w| {
+                json::object(&[
+                    ("start", w.start.to_string()),
+                    ("min", w.min.to_string()),
+                    ("max", w.max.to_string()),
+                    ("mean", format!("{:.3}", w.mean())),
+                ])
+            })
+            .collect();
+        let anomalies: Vec<String> = self
+            .anomalies
+            .iter()
+            .map(|a| {
+                json::object(&[
+                    ("line", a.line.to_string()),
+                    ("depth", a.depth.to_string()),
+                    ("expected", format!("{:.3}", a.expected)),
+                ])
+            })
+            .collect();
+        let drop = match self.largest_drop {
+            Some(drop) => json::object(&[
+                ("line", drop.line.to_string()),
+                ("from", drop.from.to_string()),
+                ("to", drop.to.to_string()),
+            ]),
+            None => String::from("null"),
+        };
+
+        json::object(&[
+            ("readings", self.readings.to_string()),
+            ("increases", self.increases.to_string()),
+            ("decreases", self.decreases.to_string()),
+            ("unchanged", self.unchanged.to_string()),
+            (
+                "longest_rise",
+                json::object(&[
+                    ("start", self.longest_rise.start.to_string()),
+                    ("length", self.longest_rise.length.to_string()),
+                ]),
+            ),
+            ("largest_drop", drop),
+            ("windows", json::array(&windows)),
+            ("anomalies", json::array(&anomalies)),
+        ])
+    }
+
+    // A summary, then the anomalies, then every window if `windows` is set
+    // (there's one for nearly every line, so they're a lot to scroll past).
+    pub fn print(&self, windows: bool) {
+        let rise = &self.longest_rise;
+        let drop = match self.largest_drop {
+            Some(d) => format!("{} on line {} ({} to {})", d.size(), d.line, d.from, d.to),
+            None => String::from("none"),
+        };
+        let summary = [
+            ("readings", self.readings.to_string()),
+            ("increases", self.increases.to_string()),
+            ("decreases", self.decreases.to_string()),
+            ("unchanged", self.unchanged.to_string()),
+            (
+                "longest rise",
+                format!("{} readings from line {}", rise.length, rise.start),
+            ),
+            ("largest drop", drop),
+            ("anomalies", self.anomalies.len().to_string()),
+        ]
+        .map(|(name, value)| vec![name.to_string(), value]);
+        table::print(&["Measure", "Value"], &summary);
+
+        if !self.anomalies.is_empty() {
+            println!();
+            let rows: Vec<Vec<String>> = self
+                .anomalies
+                .iter()
+                .map(|a| {
+                    vec![
+                        a.line.to_string(),
+                        a.depth.to_string(),
+                        format!("{:.1}", a.expected),
+                    ]
+                })
+                .collect();
+            table::print(&["Line", "Depth", "Expected"], &rows);
+        }
+
+        if windows {
+            println!();
+            let rows: Vec<Vec<String>> = self
+                .windows
+                .iter()
+                .map(|w| {
+                    vec![
+                        w.start.to_string(),
+                        w.min.to_string(),
+                        w.max.to_string(),
+                        format!("{:.1}", w.mean()),
+                    ]
+                })
+                .collect();
+            table::print(&["Line", "Min", "Max", "Mean"], &rows);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DEPTHS: [u64; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    fn width(n: usize) -> NonZeroUsize {
+        NonZeroUsize::new(n).unwrap()
+    }
+
+    #[test]
+    fn analyse_test() {
+        let analysis = analyse(&DEPTHS, width(3), 25);
+
+        assert!(analysis.readings == 10);
+        assert!((analysis.increases, analysis.decreases, analysis.unchanged) == (7, 2, 0));
+        assert!(
+            analysis.longest_rise
+                == Run {
+                    start: 1,
+                    length: 4
+                }
+        );
+        assert!(
+            analysis.largest_drop
+                == Some(Fall {
+                    line: 5,
+                    from: 210,
+                    to: 200
+                })
+        );
+
+        assert!(analysis.windows.len() == 8);
+        let first = analysis.windows[0];
+        assert!((first.start, first.min, first.max, first.sum) == (1, 199, 208, 607));
+        let last = analysis.windows[7];
+        assert!((last.start, last.min, last.max, last.sum) == (8, 260, 269, 792));
+
+        // 240 after 210, 200 and 207, then 269 after 200, 207 and 240.
+        let lines: Vec<usize> = analysis.anomalies.iter().map(|a| a.line).collect();
+        assert!(lines == vec![7, 8]);
+        assert!(analysis.anomalies[0].expected == 617.0 / 3.0);
+    }
+
+    #[test]
+    fn edges_test() {
+        let analysis = analyse(&[], width(3), 0);
+        assert!(analysis.longest_rise.length == 0 && analysis.largest_drop.is_none());
+        assert!(analysis.windows.is_empty());
+
+        let analysis = analyse(&[5, 5, 5], width(1), 0);
+        assert!(analysis.unchanged == 2 && analysis.longest_rise.length == 1);
+        assert!(analysis.windows.iter().all(|w| w.min == 5 && w.max == 5));
+        assert!(analysis.anomalies.is_empty());
+
+        // Deep enough to overflow a u64 if the sums were kept in one.
+        let deep = [u64::MAX, u64::MAX, 0];
+        let analysis = analyse(&deep, width(2), u64::MAX);
+        assert!(analysis.windows[0].sum == 2 * u64::MAX as u128);
+        assert!(analysis.anomalies.is_empty());
+        let analysis = analyse(&deep, width(2), u64::MAX - 1);
+        assert!(analysis.anomalies.len() == 1 && analysis.anomalies[0].line == 3);
+    }
+
+    #[test]
+    fn json_test() {
+        let json = analyse(&[3, 1], width(2), 0).to_json();
+
+        assert!(
+            json == "{\"readings\":2,\"increases\":0,\"decreases\":1,\"unchanged\":0,\
+                     \"longest_rise\":{\"start\":1,\"length\":1},\
+                     \"largest_drop\":{\"line\":2,\"from\":3,\"to\":1},\
+                     \"windows\":[\n  {\"start\":1,\"min\":1,\"max\":3,\"mean\":2.000}\n],\
+                     \"anomalies\":[]}"
+        );
+    }
+}
@@ -0,0 +1,245 @@
+use common::{Example, Located, Rng, Solution, Source};
+use std::collections::VecDeque;
+use std::io::BufRead;
+use std::num::NonZeroUsize;
+
+pub mod analysis;
+
+pub use analysis::{analyse, Analysis};
+
+#[derive(Debug, PartialEq)]
+pub enum DepthParseError {
+    Empty,
+    Depth(String),
+}
+
+impl std::error::Error for DepthParseError {}
+
+impl std::fmt::Display for DepthParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            DepthParseError::Empty => write!(f, "no depths to read"),
+            DepthParseError::Depth(s) => write!(f, "\"{}\" isn't a depth", s),
+        }
+    }
+}
+
+fn parse_depth(n: usize, line: &str) -> Result<u64, Located<DepthParseError>> {
+    line.parse::<u64>()
+        .map_err(|_| Located::new(n + 1, DepthParseError::Depth(line.to_string())))
+}
+
+fn parse(lines: Vec<String>) -> Result<Vec<u64>, Box<dyn std::error::Error>> {
+    let depths = lines
+        .iter()
+        .enumerate()
+        .map(|(n, line)| parse_depth(n, line))
+        .collect::<Result<Vec<u64>, _>>()?;
+
+    if depths.is_empty() {
+        return Err(DepthParseError::Empty.into());
+    }
+    Ok(depths)
+}
+
+// The last `size` depths, which is all it takes to tell whether a window got
+// deeper.  Sliding the window along one drops the oldest depth and adds the
+// newest, and everything in between is in both sums, so
+// 199 + 200 + 208 < 200 + 208 + 210 is just 199 < 210.
+#[derive(Debug, Clone)]
+pub struct Window {
+    size: usize,
+    depths: VecDeque<u64>,
+}
+
+impl Window {
+    pub fn new(size: NonZeroUsize) -> Window {
+        let size = size.get();
+
+        Window {
+            size,
+            depths: VecDeque::with_capacity(size + 1),
+        }
+    }
+
+    // Whether the window is deeper with `depth` in it than it was before,
+    // or None until there's been a whole window to compare with.
+    pub fn push(&mut self, depth: u64) -> Option<bool> {
+        self.depths.push_back(depth);
+        if self.depths.len() <= self.size {
+            return None;
+        }
+        self.depths.pop_front().map(|oldest| oldest < depth)
+    }
+}
+
+// For each depth after the first full window, whether the window got deeper.
+// It only ever holds one window's worth, so it's fine on a feed that never
+// ends.
+#[derive(Debug, Clone)]
+pub struct WindowIncreases<I> {
+    depths: I,
+    window: Window,
+}
+
+impl<I: Iterator<Item = u64>> Iterator for WindowIncreases<I> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        loop {
+            let depth = self.depths.next()?;
+            if let Some(increased) = self.window.push(depth) {
+                return Some(increased);
+            }
+        }
+    }
+}
+
+pub trait Sweep: Iterator<Item = u64> + Sized {
+    fn window_increases(self, size: NonZeroUsize) -> WindowIncreases<Self> {
+        WindowIncreases {
+            depths: self,
+            window: Window::new(size),
+        }
+    }
+}
+
+impl<I: Iterator<Item = u64>> Sweep for I {}
+
+// How many times a window of `size` depths got deeper, reading a report a line
+// at a time rather than all at once.
+pub fn count_increases(
+    report: impl BufRead,
+    size: NonZeroUsize,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let mut window = Window::new(size);
+    let mut increases = 0;
+    let mut empty = true;
+
+    for (n, line) in report.lines().enumerate() {
+        let depth = parse_depth(n, &line?)?;
+        if window.push(depth) == Some(true) {
+            increases += 1;
+        }
+        empty = false;
+    }
+
+    if empty {
+        return Err(DepthParseError::Empty.into());
+    }
+    Ok(increases)
+}
+
+// Part 1 compares each depth with the one before it, part 2 windows of 3.
+const PAIRS: NonZeroUsize = NonZeroUsize::MIN;
+const THREES: NonZeroUsize = NonZeroUsize::new(3).unwrap();
+
+const EXAMPLE: &str = "199
+200
+208
+210
+200
+207
+240
+269
+260
+263";
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: usize = 1;
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("7"),
+        part2: Some("5"),
+    }];
+
+    type Input = Vec<u64>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input, Box<dyn std::error::Error>> {
+        parse(lines)
+    }
+
+    fn part1(depths: &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+        // (199, 200), (200, 208), (208, 210) ...
+        let increases = depths.iter().copied().window_increases(PAIRS);
+
+        Ok(increases.filter(|&increased| increased).count())
+    }
+
+    fn part2(depths: &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+        // (199 + 200 + 208, 200 + 208 + 210), (200 + 208 + 210, 208 + 210 + 200) ...
+        let increases = depths.iter().copied().window_increases(THREES);
+
+        Ok(increases.filter(|&increased| increased).count())
+    }
+
+    // `size` depths, wandering up and down like the sea floor.
+    fn generate(rng: &mut Rng, size: Option<usize>) -> Vec<String> {
+        let mut depth = rng.between(100, 200);
+
+        (0..size.unwrap_or(2000))
+            .map(|_| {
+                depth = (depth + rng.between(-20, 30)).max(0);
+                depth.to_string()
+            })
+            .collect()
+    }
+}
+
+pub fn run(source: Option<Source>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    common::run::<Day1>(source)
+}
+
+common::solution_tests!(Day1);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn width(n: usize) -> NonZeroUsize {
+        NonZeroUsize::new(n).unwrap()
+    }
+
+    #[test]
+    fn window_test() {
+        let depths = || parse(EXAMPLE.lines().map(String::from).collect()).unwrap();
+
+        // Windows as wide as the report, or wider, never get to compare.
+        let counts: Vec<usize> = [1, 2, 3, 9, 10, 11]
+            .iter()
+            .map(|&size| {
+                depths()
+                    .into_iter()
+                    .window_increases(width(size))
+                    .filter(|&i| i)
+                    .count()
+            })
+            .collect();
+        assert!(counts == vec![7, 5, 5, 1, 0, 0]);
+    }
+
+    #[test]
+    fn unbounded_test() {
+        // 0, 0, 0, 1, 0, 2 ... goes on forever.
+        let feed = (0..).flat_map(|n| [0, n]);
+        let increases: Vec<bool> = feed.window_increases(width(2)).take(4).collect();
+
+        assert!(increases == vec![false, true, false, true]);
+    }
+
+    #[test]
+    fn stream_test() {
+        assert!(count_increases(EXAMPLE.as_bytes(), PAIRS).unwrap() == 7);
+        assert!(count_increases(EXAMPLE.as_bytes(), THREES).unwrap() == 5);
+
+        let e = count_increases("1\n2\nthree\n".as_bytes(), PAIRS).unwrap_err();
+        assert!(e.to_string() == "line 3: \"three\" isn't a depth");
+        let e = count_increases("".as_bytes(), PAIRS).unwrap_err();
+        assert!(e.to_string() == "no depths to read");
+    }
+}
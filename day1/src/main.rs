@@ -1,46 +1,49 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::PathBuf;
-
-fn parse(buf: &mut BufReader<File>) -> Result<Vec<u64>, Box<dyn std::error::Error>> {
-    let mut depths: Vec<u64> = vec![];
-
-    // We could check for increases here so we don't have to read the vec again.
-    // That is more performant, but makes it harder to make changes later.
-    for line in buf.lines() {
-        depths.push(line?.parse::<u64>()?);
+use common::{Args, Solution, Subcommand};
+use day1::Day1;
+use std::num::NonZeroUsize;
+
+const USAGE: &str = "usage: day1 [<path>]
+       day1 report [--json] [--windows] [--window <n>] [--tolerance <n>] [<path>]
+
+<path> is the puzzle input, or - for stdin
+
+--json       print the report as JSON
+--windows    list the min, max and mean of every window too
+--window     how many readings wide the windows are (default 3)
+--tolerance  how far a reading can be from the mean of the window before it
+             without being an anomaly (default 50)";
+
+const COMMANDS: &[Subcommand] = &[Subcommand {
+    name: "report",
+    switches: &["--json", "--windows"],
+    values: &["--window", "--tolerance"],
+    arguments: &[],
+}];
+
+const DEFAULT_WINDOW: NonZeroUsize = NonZeroUsize::new(3).unwrap();
+const DEFAULT_TOLERANCE: u64 = 50;
+
+fn report(args: &Args) -> Result<(), Box<dyn std::error::Error>> {
+    let window = args.value("--window")?.unwrap_or(DEFAULT_WINDOW);
+    let tolerance = args.value("--tolerance")?.unwrap_or(DEFAULT_TOLERANCE);
+    let depths = Day1::parse(common::load::<Day1>(args.source())?)?;
+    let analysis = day1::analyse(&depths, window, tolerance);
+
+    if args.switch("--json") {
+        println!("{}", analysis.to_json());
+    } else {
+        analysis.print(args.switch("--windows"));
     }
-    Ok(depths)
+    Ok(())
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mut path = PathBuf::new();
-    path.push(std::env::var("CARGO_MANIFEST_DIR")?);
-    path.push("input");
-
-    let input = File::open(path)?;
-    let mut reader = BufReader::new(input);
-
-    let depths = parse(&mut reader)?;
+fn main() {
+    let args = common::args::parse::<Day1>(COMMANDS, USAGE);
 
-    assert!(depths.len() > 0);
+    let result = match args.command() {
+        Some("report") => report(&args),
+        _ => common::answer::<Day1>(args.path()),
+    };
 
-    // (199, 200), (200, 208), (208, 210) ...
-    let depth_pairs = depths.iter().zip(depths.iter().skip(1));
-
-    println!(
-        "Part 1: {}",
-        depth_pairs.clone().filter(|(x, y)| x < y).count()
-    );
-
-    // ((199, 200), 208), ((200, 208), 210), ((208, 210), 200) ...
-    let depth_triplets = depth_pairs.zip(depths.iter().skip(2));
-    // 607, 618, 618, 617 ...
-    let windows: Vec<u64> = depth_triplets.map(|((x, y), z)| x + y + z).collect();
-    // (607, 618), (618, 618), (618, 617) ...
-    let window_pairs = windows.iter().zip(windows.iter().skip(1));
-
-    println!("Part 2: {}", window_pairs.filter(|(x, y)| x < y).count());
-
-    Ok(())
+    common::exit_on_error::<Day1>(result);
 }
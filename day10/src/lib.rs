@@ -0,0 +1,169 @@
+use common::{Example, Located, Rng, Solution, Source};
+use std::collections::BTreeMap;
+
+#[derive(Debug, PartialEq)]
+pub enum ChunkError {
+    Empty,
+    // column, and what was there
+    Character(usize, char),
+    Complete,
+}
+
+impl std::error::Error for ChunkError {}
+
+impl std::fmt::Display for ChunkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ChunkError::Empty => write!(f, "no lines to check"),
+            ChunkError::Character(column, c) => {
+                write!(f, "\"{}\" at column {} isn't a bracket", c, column)
+            }
+            ChunkError::Complete => write!(f, "no incomplete lines to score"),
+        }
+    }
+}
+
+fn parse(lines: Vec<String>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    for (n, line) in lines.iter().enumerate() {
+        if let Some((column, c)) = line
+            .chars()
+            .enumerate()
+            .find(|(_, c)| !"()[]{}<>".contains(*c))
+        {
+            return Err(Located::new(n + 1, ChunkError::Character(column + 1, c)).into());
+        }
+    }
+
+    if lines.is_empty() {
+        return Err(ChunkError::Empty.into());
+    }
+    Ok(lines)
+}
+
+// Returns the total corruption score, and the completion score of each incomplete line.
+pub fn check_syntax(lines: &[String]) -> (u64, Vec<u64>) {
+    let map = BTreeMap::from([('[', ']'), ('{', '}'), ('(', ')'), ('<', '>')]);
+    let corrupted_points = BTreeMap::from([(')', 3), (']', 57), ('}', 1197), ('>', 25137)]);
+    let incomplete_points = BTreeMap::from([(')', 1), (']', 2), ('}', 3), ('>', 4)]);
+    let mut total = 0;
+    let mut incomplete_scores = vec![];
+
+    for line in lines {
+        let mut opens = vec![];
+        let mut corrupted = false;
+        for c in line.chars() {
+            if map.contains_key(&c) {
+                opens.push(c);
+            } else {
+                // A closer with nothing open is as corrupt as one that doesn't match.
+                if opens.pop().map(|last| map[&last]) != Some(c) {
+                    total += corrupted_points[&c];
+                    corrupted = true;
+                    break;
+                }
+            }
+        }
+        if !corrupted {
+            let mut completion_string = String::new();
+            let mut completion_score: u64 = 0;
+            while let Some(opener) = opens.pop() {
+                let closer = map[&opener];
+                completion_string.push(closer);
+                completion_score *= 5;
+                completion_score += incomplete_points[&closer];
+            }
+            incomplete_scores.push(completion_score);
+        }
+    }
+
+    incomplete_scores.sort();
+
+    (total, incomplete_scores)
+}
+
+const EXAMPLE: &str = "[({(<(())[]>[[{[]{<()<>>
+[(()[<>])]({[<{<<[]>>(
+{([(<{}[<>[]}>{[]{[(<()>
+(((({<>}<{<{<>}{[]{[]{}
+[[<[([]))<([[{}[[()]]]
+[{[{({}]{}}([{[{{{}}([]
+{<[[]]>}<{[{[{[]{()[[[]
+[<(<(<(<{}))><([]([]()
+<{([([[(<>()){}]>(<<{{
+<{([{{}}[<[[[<>{}]]]>[]]";
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: usize = 10;
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("26397"),
+        part2: Some("288957"),
+    }];
+
+    type Input = Vec<String>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input, Box<dyn std::error::Error>> {
+        parse(lines)
+    }
+
+    fn part1(lines: &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+        Ok(check_syntax(lines).0)
+    }
+
+    fn part2(lines: &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+        let incomplete_scores = check_syntax(lines).1;
+
+        Ok(*incomplete_scores
+            .get(incomplete_scores.len() / 2)
+            .ok_or(ChunkError::Complete)?)
+    }
+
+    // `size` lines, about half of them corrupted and the rest incomplete.
+    fn generate(rng: &mut Rng, size: Option<usize>) -> Vec<String> {
+        let pairs = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+
+        (0..size.unwrap_or(100))
+            .map(|_| {
+                let length = rng.between(10, 110) as usize;
+                let mut line = String::new();
+                let mut closers = vec![];
+
+                // Stopping with something still open makes it incomplete.
+                while line.len() < length || closers.is_empty() {
+                    match closers.pop() {
+                        Some(closer) if rng.chance(2, 5) => line.push(closer),
+                        closer => {
+                            closers.extend(closer);
+                            let &(open, close) = rng.choose(&pairs);
+                            line.push(open);
+                            closers.push(close);
+                        }
+                    }
+                }
+
+                if rng.chance(1, 2) {
+                    let expected = closers.pop();
+                    let wrong: Vec<char> = pairs
+                        .iter()
+                        .map(|&(_, close)| close)
+                        .filter(|&close| Some(close) != expected)
+                        .collect();
+                    line.push(*rng.choose(&wrong));
+                }
+
+                line
+            })
+            .collect()
+    }
+}
+
+pub fn run(source: Option<Source>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    common::run::<Day10>(source)
+}
+
+common::solution_tests!(Day10);
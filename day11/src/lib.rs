@@ -0,0 +1,183 @@
+use common::image::Colour;
+use common::{generate, Example, Grid, Rng, Simulation, Solution, Source, Visualise};
+
+fn parse(lines: Vec<String>) -> Result<Grid<u8>, Box<dyn std::error::Error>> {
+    let digits = Grid::parse(&lines, |c| c.to_digit(10).map(|d| d as u8))?;
+
+    Ok(digits)
+}
+
+fn flash(octopi: &mut Grid<u8>, x: usize, y: usize) {
+    let adjacents: Vec<(usize, usize)> = octopi.neighbours8(x, y).collect();
+
+    for adj_pos in adjacents {
+        octopi[adj_pos] += 1;
+        // It's important that we only check 10, because each octopus can only flash once.
+        if octopi[adj_pos] == 10 {
+            // Look ma, I did a recursion!
+            flash(octopi, adj_pos.0, adj_pos.1);
+        }
+    }
+}
+
+pub fn process_step(octopi: Grid<u8>) -> Grid<u8> {
+    let mut octopi = octopi.map(|x| x + 1);
+
+    let flashers: Vec<(usize, usize)> = octopi
+        .positions()
+        .filter(|&pos| octopi[pos] == 10)
+        .collect();
+
+    for (x, y) in flashers {
+        flash(&mut octopi, x, y);
+    }
+
+    octopi.map(|&x| if x >= 10 { 0 } else { x })
+}
+
+// The octopuses flashing away until they all flash at once.
+pub struct Octopuses {
+    octopi: Grid<u8>,
+    steps: usize,
+    flashes: usize,
+}
+
+impl Simulation for Octopuses {
+    fn step(&mut self) -> bool {
+        if self.steps > 0 && self.octopi.iter().all(|&x| x == 0) {
+            return false;
+        }
+        self.octopi = process_step(self.octopi.clone());
+        self.steps += 1;
+        self.flashes += self.octopi.iter().filter(|&x| *x == 0).count();
+
+        true
+    }
+
+    fn render(&self) -> String {
+        let caption = format!("step {}, {} flashes so far", self.steps, self.flashes);
+        // The ones that just flashed stand out more as blanks than as 0s.
+        let octopi = self.octopi.render(|&x| match x {
+            0 => ' ',
+            _ => char::from_digit(x as u32, 10).unwrap(),
+        });
+
+        format!("{}\n{}", caption, octopi)
+    }
+
+    // A heat map, from cold and dark to hot orange, with the ones that just
+    // flashed in white.
+    fn picture(&self) -> Option<Grid<Colour>> {
+        let (cold, hot) = (Colour(20, 20, 60), Colour(255, 140, 0));
+
+        Some(self.octopi.map(|&x| match x {
+            0 => Colour::WHITE,
+            _ => cold.blend(hot, x as usize - 1, 8),
+        }))
+    }
+}
+
+const EXAMPLE: &str = "5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526";
+
+pub struct Day11;
+
+// How many octopuses' steps generate can simulate looking for a grid that
+// all flashes at once: 20 tries of 1000 steps at the default size.
+const SEARCH_CELLS: usize = 20 * 1000 * 10 * 10;
+
+impl Solution for Day11 {
+    const DAY: usize = 11;
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("1656"),
+        part2: Some("195"),
+    }];
+
+    type Input = Grid<u8>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input, Box<dyn std::error::Error>> {
+        parse(lines)
+    }
+
+    fn part1(octopi: &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+        let mut octopi = octopi.clone();
+        let mut flash_count = 0;
+
+        for _ in 0..100 {
+            octopi = process_step(octopi);
+            flash_count += octopi.iter().filter(|&x| *x == 0).count();
+        }
+
+        Ok(flash_count)
+    }
+
+    fn part2(octopi: &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+        let mut octopi = octopi.clone();
+        let mut steps = 0;
+
+        while octopi.iter().any(|&x| x != 0) {
+            octopi = process_step(octopi);
+            steps += 1;
+        }
+
+        Ok(steps)
+    }
+
+    // A `size` x `size` grid of octopuses.  Plenty of grids settle into a
+    // cycle where they never all flash at once, which would leave part 2
+    // going forever, so try a few until one does.  The bigger the grid the
+    // less likely that is, and the fewer steps SEARCH_CELLS lets us look
+    // for, so past a point we settle for one that might not.
+    fn generate(rng: &mut Rng, size: Option<usize>) -> Vec<String> {
+        let size = size.unwrap_or(10).max(1);
+        let mut steps = SEARCH_CELLS / (size * size);
+        let mut lines = generate::digits(rng, size, size, 0, 9);
+
+        for _ in 0..20 {
+            let Ok(mut octopi) = parse(lines.clone()) else {
+                break;
+            };
+            for _ in 0..steps.min(1000) {
+                octopi = process_step(octopi);
+                steps -= 1;
+                if octopi.iter().all(|&x| x == 0) {
+                    return lines;
+                }
+            }
+            if steps == 0 {
+                break;
+            }
+            lines = generate::digits(rng, size, size, 0, 9);
+        }
+
+        lines
+    }
+}
+
+impl Visualise for Day11 {
+    fn simulation(octopi: &Self::Input) -> Box<dyn Simulation> {
+        Box::new(Octopuses {
+            octopi: octopi.clone(),
+            steps: 0,
+            flashes: 0,
+        })
+    }
+}
+
+pub fn run(source: Option<Source>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    common::run::<Day11>(source)
+}
+
+common::solution_tests!(Day11);
@@ -0,0 +1,250 @@
+use common::{Example, Located, Rng, Solution, Source};
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Debug, PartialEq)]
+pub enum CaveError {
+    Separator,
+    // column, and what was there
+    Name(usize, String),
+    // Two big caves next to each other means paths that never end.
+    BigCaves(String, String),
+    Missing(&'static str),
+}
+
+impl std::error::Error for CaveError {}
+
+impl std::fmt::Display for CaveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CaveError::Separator => write!(f, "expected \"<cave>-<cave>\""),
+            CaveError::Name(column, s) => {
+                write!(f, "\"{}\" at column {} isn't a cave name", s, column)
+            }
+            CaveError::BigCaves(a, b) => write!(
+                f,
+                "big caves {} and {} are connected, so there are endless paths",
+                a, b
+            ),
+            CaveError::Missing(name) => write!(f, "there's no {} cave", name),
+        }
+    }
+}
+
+fn is_lower(name: &str) -> bool {
+    name.chars().filter(|c| c.is_lowercase()).count() == name.len()
+}
+
+fn is_upper(name: &str) -> bool {
+    name.chars().filter(|c| c.is_uppercase()).count() == name.len()
+}
+
+fn parse_connection(line: &str) -> Result<(String, String), CaveError> {
+    let (a, b) = line.split_once("-").ok_or(CaveError::Separator)?;
+
+    for name in [a, b] {
+        if name.is_empty() || !(is_lower(name) || is_upper(name)) {
+            return Err(CaveError::Name(
+                common::parse::column(line, name),
+                name.to_string(),
+            ));
+        }
+    }
+    if is_upper(a) && is_upper(b) {
+        return Err(CaveError::BigCaves(a.to_string(), b.to_string()));
+    }
+
+    Ok((a.to_string(), b.to_string()))
+}
+
+fn parse(lines: Vec<String>) -> Result<BTreeMap<String, Vec<String>>, Box<dyn std::error::Error>> {
+    let mut graph: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    for (n, line) in lines.iter().enumerate() {
+        let (a, b) = parse_connection(line).map_err(|e| Located::new(n + 1, e))?;
+        graph.entry(a.clone()).or_insert(vec![]).push(b.clone());
+        graph.entry(b).or_insert(vec![]).push(a);
+    }
+
+    for name in ["start", "end"] {
+        if !graph.contains_key(name) {
+            return Err(CaveError::Missing(name).into());
+        }
+    }
+    Ok(graph)
+}
+
+pub fn explore(
+    graph: &BTreeMap<String, Vec<String>>,
+    path: Vec<String>,
+    doubled: bool,
+) -> Vec<Vec<String>> {
+    let current_node = path.last().unwrap();
+    if current_node == "end" {
+        return vec![path];
+    }
+
+    let mut new_paths = vec![];
+
+    for node in &graph[current_node] {
+        let mut doubled = doubled;
+        if is_lower(node) && path.contains(node) {
+            if doubled || node == "start" {
+                // We can't go there again, so skip.
+                continue;
+            } else {
+                // This is our one double lowercase visit
+                doubled = true;
+            }
+        }
+        let mut new_path = path.clone();
+        new_path.push(node.to_string());
+
+        // Recursion two days in a row!
+        new_paths.extend(explore(graph, new_path, doubled));
+    }
+
+    new_paths
+}
+
+const EXAMPLE1: &str = "start-A
+start-b
+A-c
+A-b
+b-d
+A-end
+b-end";
+
+const EXAMPLE2: &str = "dc-end
+HN-start
+start-kj
+dc-start
+dc-HN
+LN-dc
+HN-end
+kj-sa
+kj-HN
+kj-dc";
+
+const EXAMPLE3: &str = "fs-end
+he-DX
+fs-he
+start-DX
+pj-DX
+end-zg
+zg-sl
+zg-pj
+pj-he
+RW-he
+fs-DX
+pj-RW
+zg-RW
+start-pj
+he-WI
+zg-he
+pj-fs
+start-RW";
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: usize = 12;
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: EXAMPLE1,
+            part1: Some("10"),
+            part2: Some("36"),
+        },
+        Example {
+            input: EXAMPLE2,
+            part1: Some("19"),
+            part2: Some("103"),
+        },
+        Example {
+            input: EXAMPLE3,
+            part1: Some("226"),
+            part2: Some("3509"),
+        },
+    ];
+
+    type Input = BTreeMap<String, Vec<String>>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input, Box<dyn std::error::Error>> {
+        parse(lines)
+    }
+
+    fn part1(graph: &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+        Ok(explore(graph, vec![String::from("start")], true).len())
+    }
+
+    fn part2(graph: &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+        Ok(explore(graph, vec![String::from("start")], false).len())
+    }
+
+    // `size` small caves and a third as many big ones, connected at random.
+    // Big caves are never next to each other, or there'd be no end to the
+    // paths.  Paths multiply quickly, so `size` doesn't need to be big.
+    fn generate(rng: &mut Rng, size: Option<usize>) -> Vec<String> {
+        let small = size.unwrap_or(10);
+        let big = (small / 3).max(1);
+
+        // Names as long as it takes to go round, two letters at least, and
+        // never start or end.
+        let mut length = 2;
+        while 26usize.pow(length) < small + big + 2 {
+            length += 1;
+        }
+        let mut names: Vec<String> = (0..)
+            .map(|n: usize| {
+                (0..length)
+                    .rev()
+                    .map(|digit| (b'a' + (n / 26usize.pow(digit) % 26) as u8) as char)
+                    .collect::<String>()
+            })
+            .filter(|name| name != "start" && name != "end")
+            .take(small + big)
+            .collect();
+        rng.shuffle(&mut names);
+        let mut caves: Vec<String> = names[..small].to_vec();
+        caves.extend(
+            names[small..small + big]
+                .iter()
+                .map(|name| name.to_uppercase()),
+        );
+
+        let mut connections = BTreeSet::new();
+        let mut connect = |a: &str, b: &str| {
+            if a == b || !(is_lower(a) || is_lower(b)) {
+                return;
+            }
+            if !connections.contains(&(b.to_string(), a.to_string())) {
+                connections.insert((a.to_string(), b.to_string()));
+            }
+        };
+
+        connect("start", rng.choose(&caves).as_str());
+        connect(rng.choose(&caves).as_str(), "end");
+        for cave in &caves {
+            connect(cave, rng.choose(&caves).as_str());
+        }
+        for _ in 0..small / 2 {
+            connect(rng.choose(&caves).as_str(), rng.choose(&caves).as_str());
+        }
+
+        let mut lines: Vec<String> = connections
+            .into_iter()
+            .map(|(a, b)| format!("{}-{}", a, b))
+            .collect();
+        rng.shuffle(&mut lines);
+
+        lines
+    }
+}
+
+pub fn run(source: Option<Source>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    common::run::<Day12>(source)
+}
+
+common::solution_tests!(Day12);
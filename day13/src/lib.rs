@@ -0,0 +1,360 @@
+use common::image::Colour;
+use common::{Example, Grid, Located, Rng, Simulation, Solution, Source, Visualise};
+use std::collections::BTreeSet;
+
+#[derive(Debug, PartialEq)]
+pub enum ManualError {
+    // column, and what was there
+    Coordinate(usize, String),
+    Dot,
+    OffPage(usize, usize),
+    Fold,
+    // fold position, and how big the page is that way when we get to it
+    Size(usize, usize),
+    NoFolds,
+}
+
+impl std::error::Error for ManualError {}
+
+impl std::fmt::Display for ManualError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ManualError::Coordinate(column, s) => {
+                write!(f, "\"{}\" at column {} isn't a coordinate", s, column)
+            }
+            ManualError::Dot => write!(f, "expected \"x,y\""),
+            ManualError::OffPage(x, y) => write!(f, "dot at {},{} is off the page", x, y),
+            ManualError::Fold => write!(f, "expected \"fold along <x or y>=<position>\""),
+            ManualError::Size(pos, size) => write!(
+                f,
+                "can't fold at {} when the page is only {} that way",
+                pos, size
+            ),
+            ManualError::NoFolds => write!(f, "no folds to make"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Fold {
+    X(usize),
+    Y(usize),
+}
+
+impl Fold {
+    fn fold(&self, page: &Grid<bool>) -> Grid<bool> {
+        match self {
+            Fold::X(pos) => fold_left(page, *pos),
+            Fold::Y(pos) => fold_up(page, *pos),
+        }
+    }
+}
+
+fn parse_number(line: &str, s: &str) -> Result<usize, ManualError> {
+    s.parse::<usize>()
+        .map_err(|_| ManualError::Coordinate(common::parse::column(line, s), s.to_string()))
+}
+
+fn parse_dot(line: &str) -> Result<(usize, usize), ManualError> {
+    let (x, y) = line.split_once(",").ok_or(ManualError::Dot)?;
+
+    Ok((parse_number(line, x)?, parse_number(line, y)?))
+}
+
+fn parse_fold(line: &str) -> Result<Fold, ManualError> {
+    let fold = line.strip_prefix("fold along ").ok_or(ManualError::Fold)?;
+
+    match fold.split_once("=") {
+        Some(("x", val)) => Ok(Fold::X(parse_number(line, val)?)),
+        Some(("y", val)) => Ok(Fold::Y(parse_number(line, val)?)),
+        _ => Err(ManualError::Fold),
+    }
+}
+
+// Parse each line with `f`, numbering them from `first` if it goes wrong.
+fn parse_lines<T>(
+    lines: &[String],
+    first: usize,
+    f: impl Fn(&str) -> Result<T, ManualError>,
+) -> Result<Vec<T>, Located<ManualError>> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(n, line)| f(line).map_err(|e| Located::new(first + n, e)))
+        .collect()
+}
+
+// The first fold each way is down the middle of the page, which is how we
+// know how big it is.  Without one, the page ends at the last dot.
+fn page_size(dots: &[(usize, usize)], folds: &[Fold]) -> (usize, usize) {
+    let x = folds.iter().find_map(|fold| match fold {
+        Fold::X(val) => Some(val * 2 + 1),
+        _ => None,
+    });
+    let y = folds.iter().find_map(|fold| match fold {
+        Fold::Y(val) => Some(val * 2 + 1),
+        _ => None,
+    });
+
+    (
+        x.unwrap_or_else(|| dots.iter().map(|d| d.0 + 1).max().unwrap_or(0)),
+        y.unwrap_or_else(|| dots.iter().map(|d| d.1 + 1).max().unwrap_or(0)),
+    )
+}
+
+fn parse_coords(dots: &[(usize, usize)], x: usize, y: usize) -> Grid<bool> {
+    let mut page = Grid::new(x, y, false);
+
+    dots.iter().for_each(|&(x, y)| page[(x, y)] = true);
+
+    page
+}
+
+pub fn fold_left(page: &Grid<bool>, x_size: usize) -> Grid<bool> {
+    let y_size = page.height();
+    let mut new_page = Grid::new(x_size, y_size, false);
+
+    for y in 0..y_size {
+        for x in 0..x_size {
+            new_page[(x, y)] = page[(x, y)] || page[((x_size * 2) - x, y)];
+        }
+    }
+
+    new_page
+}
+
+pub fn fold_up(page: &Grid<bool>, y_size: usize) -> Grid<bool> {
+    let x_size = page.width();
+    let mut new_page = Grid::new(x_size, y_size, false);
+
+    for y in 0..y_size {
+        for x in 0..x_size {
+            new_page[(x, y)] = page[(x, y)] || page[(x, y_size * 2 - y)];
+        }
+    }
+
+    new_page
+}
+
+// The page folding up one fold at a time.
+pub struct Folding {
+    page: Grid<bool>,
+    folds: Vec<Fold>,
+    done: usize,
+}
+
+impl Simulation for Folding {
+    fn step(&mut self) -> bool {
+        match self.folds.get(self.done) {
+            Some(fold) => {
+                self.page = fold.fold(&self.page);
+                self.done += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn render(&self) -> String {
+        let caption = match self.folds.get(self.done) {
+            Some(Fold::X(x)) => format!("{} folds made, next along x={}", self.done, x),
+            Some(Fold::Y(y)) => format!("{} folds made, next along y={}", self.done, y),
+            None => format!("all {} folds made", self.done),
+        };
+
+        format!(
+            "{}\n{}",
+            caption,
+            self.page.render(|&x| if x { '#' } else { '.' })
+        )
+    }
+
+    fn picture(&self) -> Option<Grid<Colour>> {
+        Some(
+            self.page
+                .map(|&x| if x { Colour::WHITE } else { Colour(10, 30, 60) }),
+        )
+    }
+}
+
+const EXAMPLE: &str = "6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
+
+fold along y=7
+fold along x=5";
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: usize = 13;
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("17"),
+        part2: Some("#####\n#...#\n#...#\n#...#\n#####\n.....\n....."),
+    }];
+
+    // The page of dots, and the folds to make in order.
+    type Input = (Grid<bool>, Vec<Fold>);
+    type Part1 = usize;
+    type Part2 = String;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input, Box<dyn std::error::Error>> {
+        let blank = lines
+            .iter()
+            .position(|l| l.is_empty())
+            .unwrap_or(lines.len());
+        let coords = parse_lines(&lines[..blank], 1, parse_dot)?;
+        let folds = parse_lines(lines.get(blank + 1..).unwrap_or(&[]), blank + 2, parse_fold)?;
+
+        let (mut max_x, mut max_y) = page_size(&coords, &folds);
+
+        if let Some(n) = coords.iter().position(|&(x, y)| x >= max_x || y >= max_y) {
+            let (x, y) = coords[n];
+            return Err(Located::new(n + 1, ManualError::OffPage(x, y)).into());
+        }
+        if folds.is_empty() {
+            return Err(ManualError::NoFolds.into());
+        }
+
+        let page = parse_coords(&coords, max_x, max_y);
+
+        // Make sure every fold fits the page as it'll be by then.
+        for (n, fold) in folds.iter().enumerate() {
+            let (size, pos) = match *fold {
+                Fold::X(pos) => (std::mem::replace(&mut max_x, pos), pos),
+                Fold::Y(pos) => (std::mem::replace(&mut max_y, pos), pos),
+            };
+            // A fold at 0 would leave nothing of the page to see.
+            if pos == 0 || pos * 2 + 1 > size {
+                return Err(Located::new(blank + 2 + n, ManualError::Size(pos, size)).into());
+            }
+        }
+
+        Ok((page, folds))
+    }
+
+    fn part1((page, folds): &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+        Ok(folds[0].fold(page).iter().filter(|&x| *x).count())
+    }
+
+    fn part2((page, folds): &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+        // This is so amusing to me I'm not changing the variable names.
+        let page = folds
+            .iter()
+            .fold(page.clone(), |page, fold| fold.fold(&page));
+
+        // The answer is ASCII art, so say how big it is and how much ink it took.
+        common::note("width", page.width());
+        common::note("height", page.height());
+        common::note("dots", page.iter().filter(|&&x| x).count());
+
+        Ok(page.render(|&x| if x { '#' } else { '.' }))
+    }
+
+    // `size` dots on a page the size of the puzzle's, with folds that bring it
+    // down to 40 x 6 like the puzzle's.  Each dot starts on one of the
+    // marks on the folded page (random, rather than letters) and is
+    // unfolded onto either side of each fold.
+    fn generate(rng: &mut Rng, size: Option<usize>) -> Vec<String> {
+        let mut axes = [vec!['x'; 5], vec!['y'; 7]].concat();
+        rng.shuffle(&mut axes);
+
+        // Work out the folds backwards from the finished page.
+        let (mut width, mut height) = (40, 6);
+        let mut folds = vec![];
+        for &axis in axes.iter().rev() {
+            let length = if axis == 'x' { &mut width } else { &mut height };
+            folds.push((axis, *length));
+            *length = *length * 2 + 1;
+        }
+
+        let marks: Vec<(usize, usize)> = (0..40 * 6)
+            .map(|n| (n % 40, n / 40))
+            .filter(|_| rng.chance(1, 3))
+            .collect();
+        let mut dots = BTreeSet::new();
+        for _ in 0..size.unwrap_or(800) {
+            let Some(&(x, y)) = marks.get(rng.below(marks.len().max(1))) else {
+                break;
+            };
+            let mut dot = (x, y);
+            for &(axis, pos) in &folds {
+                let n = if axis == 'x' { &mut dot.0 } else { &mut dot.1 };
+                if rng.chance(1, 2) {
+                    *n = pos * 2 - *n;
+                }
+            }
+            dots.insert(dot);
+        }
+
+        let mut dots: Vec<String> = dots.iter().map(|(x, y)| format!("{},{}", x, y)).collect();
+        rng.shuffle(&mut dots);
+        dots.push(String::new());
+        dots.extend(
+            folds
+                .iter()
+                .rev()
+                .map(|(axis, pos)| format!("fold along {}={}", axis, pos)),
+        );
+
+        dots
+    }
+}
+
+impl Visualise for Day13 {
+    fn simulation((page, folds): &Self::Input) -> Box<dyn Simulation> {
+        Box::new(Folding {
+            page: page.clone(),
+            folds: folds.clone(),
+            done: 0,
+        })
+    }
+}
+
+pub fn run(source: Option<Source>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    common::run::<Day13>(source)
+}
+
+common::solution_tests!(Day13);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fold_size_test() {
+        let error = |text: &str| {
+            let lines = text.lines().map(String::from).collect();
+            Day13::parse(lines).unwrap_err().to_string()
+        };
+
+        assert!(
+            error("0,0\n\nfold along x=0")
+                == "line 3: can't fold at 0 when the page is only 1 that way"
+        );
+        assert!(error("0,4\n\nfold along y=2\nfold along y=0").starts_with("line 4: "));
+        assert!(Day13::parse(vec![
+            "2,0".to_string(),
+            String::new(),
+            "fold along x=1".to_string()
+        ])
+        .is_ok());
+    }
+}
@@ -0,0 +1,213 @@
+use common::{Example, Located, Rng, Solution, Source};
+use std::collections::BTreeMap;
+
+#[derive(Debug, PartialEq)]
+pub enum PolymerError {
+    Template,
+    Rule,
+}
+
+impl std::error::Error for PolymerError {}
+
+impl std::fmt::Display for PolymerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PolymerError::Template => {
+                write!(f, "expected a template, then a blank line before the rules")
+            }
+            PolymerError::Rule => write!(f, "expected \"<pair> -> <element>\""),
+        }
+    }
+}
+
+// Map character pairs to the two pairs they'll split into.
+pub type Insertions = BTreeMap<(char, char), ((char, char), (char, char))>;
+
+fn parse_insertion(line: &str) -> Result<((char, char), char), PolymerError> {
+    let (pair, insert) = line.split_once(" -> ").ok_or(PolymerError::Rule)?;
+
+    let pair: Vec<char> = pair.chars().collect();
+    let insert: Vec<char> = insert.chars().collect();
+
+    match (&pair[..], &insert[..]) {
+        (&[a, b], &[c]) => Ok(((a, b), c)),
+        _ => Err(PolymerError::Rule),
+    }
+}
+
+// `first` is the line number of lines[0].
+fn parse_insertions(lines: &[String], first: usize) -> Result<Insertions, Located<PolymerError>> {
+    let mut insertions = Insertions::new();
+
+    for (n, line) in lines.iter().enumerate() {
+        let ((a, b), c) = parse_insertion(line).map_err(|e| Located::new(first + n, e))?;
+        insertions.insert((a, b), ((a, c), (c, b)));
+    }
+
+    Ok(insertions)
+}
+
+fn process_insertions(
+    pairs: BTreeMap<(char, char), usize>,
+    map: &Insertions,
+) -> BTreeMap<(char, char), usize> {
+    let mut update = pairs.clone();
+
+    for (pair, count) in pairs.iter() {
+        // If KV occurs 3 times, and splits into KP and PV...
+        let Some(&(first, last)) = map.get(pair) else {
+            // (a pair without a rule just stays as it is)
+            continue;
+        };
+        // there will be 3 new KPs,
+        *update.entry(first).or_default() += count;
+        // there will be 3 new PVs,
+        *update.entry(last).or_default() += count;
+        // and no KVs, because we just split them all up.
+        // We can't set this to 0, because that would destroy
+        // any new instances of this pair generated this cycle.
+        *update.entry(*pair).or_default() -= count;
+    }
+
+    update
+}
+
+fn count_letters(
+    pair_count: &BTreeMap<(char, char), usize>,
+    start: char,
+    end: char,
+) -> BTreeMap<char, usize> {
+    let mut char_count: BTreeMap<char, usize> = BTreeMap::new();
+
+    for (pair, count) in pair_count {
+        *char_count.entry(pair.0).or_default() += count;
+        *char_count.entry(pair.1).or_default() += count;
+    }
+
+    // there's two pair occurrences for every one character...
+    char_count = char_count
+        .iter()
+        .map(|(c, count)| (*c, count / 2))
+        .collect();
+
+    // ...except for the first and last character.
+    *char_count.entry(start).or_default() += 1;
+    *char_count.entry(end).or_default() += 1;
+
+    char_count
+}
+
+// Run the insertions for a number of steps, and return the difference
+// between the most and least common elements.
+pub fn polymerise(template: &str, insertions: &Insertions, steps: usize) -> usize {
+    let (Some(start_letter), Some(end_letter)) = (template.chars().next(), template.chars().last())
+    else {
+        return 0;
+    };
+
+    let mut initial_count: BTreeMap<(char, char), usize> = BTreeMap::new();
+
+    for pair in template
+        .chars()
+        .collect::<Vec<char>>()
+        .as_slice()
+        .windows(2)
+    {
+        *initial_count.entry((pair[0], pair[1])).or_default() += 1;
+    }
+
+    let final_count = (0..steps).fold(initial_count, |count, _| {
+        process_insertions(count, insertions)
+    });
+
+    let char_count = count_letters(&final_count, start_letter, end_letter);
+
+    let most = char_count.values().max().unwrap_or(&0);
+    let least = char_count.values().min().unwrap_or(&0);
+
+    most - least
+}
+
+const EXAMPLE: &str = "NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C";
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: usize = 14;
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("1588"),
+        part2: Some("2188189693529"),
+    }];
+
+    // The polymer template, and the pair insertion rules.
+    type Input = (String, Insertions);
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input, Box<dyn std::error::Error>> {
+        let template = match &lines[..] {
+            [template, blank, ..] if !template.is_empty() && blank.is_empty() => template.clone(),
+            [template] if !template.is_empty() => template.clone(),
+            _ => return Err(Located::new(1, PolymerError::Template).into()),
+        };
+
+        let insertions = parse_insertions(lines.get(2..).unwrap_or(&[]), 3)?;
+
+        Ok((template, insertions))
+    }
+
+    fn part1(
+        (template, insertions): &Self::Input,
+    ) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+        Ok(polymerise(template, insertions, 10))
+    }
+
+    fn part2(
+        (template, insertions): &Self::Input,
+    ) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+        Ok(polymerise(template, insertions, 40))
+    }
+
+    // A template `size` elements long, and a rule for every pair of the 10
+    // elements, like the puzzle.
+    fn generate(rng: &mut Rng, size: Option<usize>) -> Vec<String> {
+        let elements: Vec<char> = "BCFHKNOPSV".chars().collect();
+        let template: String = (0..size.unwrap_or(20).max(1))
+            .map(|_| *rng.choose(&elements))
+            .collect();
+
+        let mut lines = vec![template, String::new()];
+        for &a in &elements {
+            for &b in &elements {
+                lines.push(format!("{}{} -> {}", a, b, rng.choose(&elements)));
+            }
+        }
+
+        lines
+    }
+}
+
+pub fn run(source: Option<Source>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    common::run::<Day14>(source)
+}
+
+common::solution_tests!(Day14);
@@ -0,0 +1,184 @@
+use common::{generate, Example, Grid, Reference, Rng, Solution, Source};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+fn parse(lines: Vec<String>) -> Result<Grid<u32>, Box<dyn std::error::Error>> {
+    let digits = Grid::parse(&lines, |c| c.to_digit(10))?;
+
+    Ok(digits)
+}
+
+// This turned out to not actually be needed but it was nice to debug with
+fn find_actual_path(dist: &Grid<u32>) -> Vec<((usize, usize), u32)> {
+    let mut pos = (dist.width() - 1, dist.height() - 1);
+    let mut path = vec![(pos, dist[pos])];
+
+    // A 1x1 map is already there.
+    while pos != (0, 0) {
+        let mut min_pos = (usize::MAX, usize::MAX);
+        let mut min_score = u32::MAX;
+
+        for pos in dist.neighbours4(pos.0, pos.1) {
+            if dist[pos] < min_score {
+                min_pos = pos;
+                min_score = dist[pos];
+            }
+        }
+        path.push((min_pos, min_score));
+        pos = min_pos;
+    }
+    path.reverse();
+    path
+}
+
+// The slow way: keep relaxing every cell against its neighbours until
+// nothing changes.
+pub fn find_best_path(map: &Grid<u32>) -> u32 {
+    let mut distance = Grid::new(map.width(), map.height(), u32::MAX - 1);
+
+    distance[(0, 0)] = 0;
+    let mut changed = true;
+    let mut iterations = 0;
+
+    // Once we go an iteration without a modification, we're perfect
+    while changed {
+        changed = false;
+        iterations += 1;
+        for index in map.positions() {
+            for pos in map.neighbours4(index.0, index.1) {
+                if distance[pos] > distance[index] + map[pos] {
+                    distance[pos] = distance[index] + map[pos];
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    let risk = distance[(map.width() - 1, map.height() - 1)];
+    common::note("iterations", iterations);
+    common::note_with("path_length", move || find_actual_path(&distance).len());
+
+    risk
+}
+
+// Dijkstra's, taking the least risky cell we haven't finished with each time,
+// and stopping as soon as that's the bottom right.
+pub fn find_lowest_risk(map: &Grid<u32>) -> u32 {
+    let end = (map.width() - 1, map.height() - 1);
+    let mut distance = Grid::new(map.width(), map.height(), u32::MAX);
+    let mut queue = BinaryHeap::new();
+    let mut visited = 0;
+
+    distance[(0, 0)] = 0;
+    queue.push(Reverse((0, (0, 0))));
+
+    while let Some(Reverse((risk, index))) = queue.pop() {
+        // We already found a better way here.
+        if risk > distance[index] {
+            continue;
+        }
+        visited += 1;
+        if index == end {
+            break;
+        }
+        for pos in map.neighbours4(index.0, index.1) {
+            if risk + map[pos] < distance[pos] {
+                distance[pos] = risk + map[pos];
+                queue.push(Reverse((distance[pos], pos)));
+            }
+        }
+    }
+
+    let risk = distance[end];
+    common::note("visited", visited);
+    common::note_with("path_length", move || find_actual_path(&distance).len());
+
+    risk
+}
+
+// The full map is the tile we're given repeated 5 times in each direction,
+// with the risk going up by 1 for every tile away from the original.
+pub fn grow_map(map: &Grid<u32>) -> Grid<u32> {
+    let (width, height) = (map.width(), map.height());
+    let mut map_but_more_big = Grid::new(width * 5, height * 5, 0);
+
+    for (x, y) in map_but_more_big
+        .positions()
+        .collect::<Vec<(usize, usize)>>()
+    {
+        // How many tiles away from the original are we?
+        let tiles = (x / width + y / height) as u32;
+
+        let mut value = map[(x % width, y % height)] + tiles;
+        if value > 9 {
+            value = (value + 1) % 10;
+        }
+
+        map_but_more_big[(x, y)] = value;
+    }
+
+    map_but_more_big
+}
+
+const EXAMPLE: &str = "1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581";
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: usize = 15;
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("40"),
+        part2: Some("315"),
+    }];
+
+    type Input = Grid<u32>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input, Box<dyn std::error::Error>> {
+        parse(lines)
+    }
+
+    fn part1(map: &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+        Ok(find_lowest_risk(map))
+    }
+
+    fn part2(map: &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+        Ok(find_lowest_risk(&grow_map(map)))
+    }
+
+    // A `size` x `size` map of risks.
+    fn generate(rng: &mut Rng, size: Option<usize>) -> Vec<String> {
+        let size = size.unwrap_or(100);
+
+        generate::digits(rng, size, size, 1, 9)
+    }
+}
+
+impl Reference for Day15 {
+    fn reference1(map: &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+        Ok(find_best_path(map))
+    }
+
+    fn reference2(map: &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+        Ok(find_best_path(&grow_map(map)))
+    }
+}
+
+pub fn run(source: Option<Source>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    common::run::<Day15>(source)
+}
+
+common::solution_tests!(Day15);
+common::reference_tests!(Day15, [1, 2, 3, 5, 10]);
@@ -0,0 +1,661 @@
+use common::{Example, Located, Rng, Solution, Source};
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq)]
+pub enum PacketParseError {
+    Empty,
+    // column, and what was there
+    Digit(usize, char),
+    // the bit we'd got to when the transmission ran out
+    Truncated(usize),
+    Type(usize),
+    // an operator, and how many subpackets it was given
+    Operands(OperatorType, usize),
+    // the bit the literal starts at
+    Overflow(usize),
+}
+
+impl std::error::Error for PacketParseError {}
+
+impl std::fmt::Display for PacketParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PacketParseError::Empty => write!(f, "no transmission to decode"),
+            PacketParseError::Digit(column, c) => {
+                write!(f, "\"{}\" at column {} isn't a hex digit", c, column)
+            }
+            PacketParseError::Truncated(bit) => {
+                write!(
+                    f,
+                    "transmission ends in the middle of a packet, at bit {}",
+                    bit
+                )
+            }
+            PacketParseError::Type(id) => write!(f, "unknown packet type {}", id),
+            PacketParseError::Operands(op, count) => {
+                write!(f, "{:?} packet can't have {} subpackets", op, count)
+            }
+            PacketParseError::Overflow(bit) => {
+                write!(
+                    f,
+                    "literal starting at bit {} is too big to fit in 64 bits",
+                    bit
+                )
+            }
+        }
+    }
+}
+
+fn hex_to_bits(hex: &str) -> Result<Vec<usize>, PacketParseError> {
+    let mut bits = vec![];
+    let trimmed = hex.trim();
+    let start = if trimmed.is_empty() {
+        1
+    } else {
+        common::parse::column(hex, trimmed)
+    };
+
+    for (n, c) in trimmed.chars().enumerate() {
+        let n = c
+            .to_digit(16)
+            .ok_or(PacketParseError::Digit(start + n, c))?;
+        bits.extend([n >> 3 & 1, n >> 2 & 1, n >> 1 & 1, n & 1].map(|b| b as usize));
+    }
+
+    if bits.is_empty() {
+        Err(PacketParseError::Empty)
+    } else {
+        Ok(bits)
+    }
+}
+
+fn parse(lines: Vec<String>) -> Result<Vec<usize>, Box<dyn std::error::Error>> {
+    let line = lines.first().ok_or(PacketParseError::Empty)?;
+
+    Ok(hex_to_bits(line).map_err(|e| Located::new(1, e))?)
+}
+
+fn bits_to_val(bits: &[usize]) -> usize {
+    bits.iter().fold(0, |val, b| val << 1 | b)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OperatorType {
+    Sum,
+    Product,
+    Minimum,
+    Maximum,
+    GreaterThan,
+    LessThan,
+    EqualTo,
+}
+
+#[derive(Debug)]
+enum PacketType {
+    Literal(Vec<usize>),
+    Operator((OperatorType, Vec<Packet>)),
+}
+
+#[derive(Debug)]
+pub struct Packet {
+    version: usize,
+    ptype: PacketType,
+    size: usize,
+}
+
+// The `count` bits from `from` as a number, if the transmission goes that far.
+// `base` is where `bits` starts in the whole transmission, for the error.
+fn read(bits: &[usize], base: usize, from: usize, count: usize) -> Result<usize, PacketParseError> {
+    bits.get(from..from + count)
+        .map(bits_to_val)
+        .ok_or(PacketParseError::Truncated(base + bits.len()))
+}
+
+impl Packet {
+    fn new(bits: Vec<usize>) -> Result<Packet, PacketParseError> {
+        Packet::decode(&bits, 0)
+    }
+
+    // Decode the packet at the start of `bits`, which begins `base` bits into
+    // the transmission.
+    fn decode(bits: &[usize], base: usize) -> Result<Packet, PacketParseError> {
+        let version = read(bits, base, 0, 3)?;
+        let type_id = read(bits, base, 3, 3)?;
+
+        let (ptype, size) = if type_id == 4 {
+            let mut offset = 6;
+            let mut value: Vec<usize> = vec![];
+
+            loop {
+                read(bits, base, offset, 5)?;
+                value.extend(bits[offset + 1..=offset + 4].to_vec());
+                offset += 5;
+
+                // Was this our last packet?
+                if bits[offset - 5] == 0 {
+                    break;
+                }
+            }
+
+            // Leading zeroes don't count against the 64 bits.
+            if value.iter().skip_while(|&&b| b == 0).count() > usize::BITS as usize {
+                return Err(PacketParseError::Overflow(base));
+            }
+
+            (PacketType::Literal(value), offset)
+        } else {
+            let operator_type = match type_id {
+                0 => OperatorType::Sum,
+                1 => OperatorType::Product,
+                2 => OperatorType::Minimum,
+                3 => OperatorType::Maximum,
+                5 => OperatorType::GreaterThan,
+                6 => OperatorType::LessThan,
+                7 => OperatorType::EqualTo,
+                _ => return Err(PacketParseError::Type(type_id)),
+            };
+
+            let mut subpackets = vec![];
+            let mut offset = 6;
+            match read(bits, base, offset, 1)? {
+                1 => {
+                    // The next 11 bits tell us how many subpackets there are
+                    let subpacket_count = read(bits, base, 7, 11)?;
+                    // The first subpacket begins 1 bit after the count
+                    offset += 12;
+                    while subpackets.len() < subpacket_count {
+                        let new_packet = Packet::decode(&bits[offset..], base + offset)?;
+                        offset += new_packet.size;
+                        subpackets.push(new_packet);
+                    }
+                }
+                _ => {
+                    // The next 15 bits are a value for how large the remaining packets are
+                    let subpacket_size = read(bits, base, 7, 15)?;
+                    // The first subpacket begins 1 bit after the size
+                    offset += 16;
+
+                    let end = offset + subpacket_size;
+                    if end > bits.len() {
+                        return Err(PacketParseError::Truncated(base + bits.len()));
+                    }
+                    while offset < end {
+                        let new_packet = Packet::decode(&bits[offset..end], base + offset)?;
+                        offset += new_packet.size;
+                        subpackets.push(new_packet);
+                    }
+                }
+            }
+
+            // value() needs something to compare, or to take the min/max of.
+            let count = subpackets.len();
+            let fits = match operator_type {
+                OperatorType::Sum | OperatorType::Product => true,
+                OperatorType::Minimum | OperatorType::Maximum => count > 0,
+                _ => count == 2,
+            };
+            if !fits {
+                return Err(PacketParseError::Operands(operator_type, count));
+            }
+
+            (PacketType::Operator((operator_type, subpackets)), offset)
+        };
+
+        Ok(Packet {
+            version,
+            ptype,
+            size,
+        })
+    }
+
+    pub fn version(&self) -> usize {
+        self.version
+    }
+
+    // How many bits of the transmission this packet took up, including its subpackets.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    // The number carried by a literal packet, or None for operators.
+    pub fn literal(&self) -> Option<usize> {
+        match &self.ptype {
+            PacketType::Literal(val) => Some(bits_to_val(val)),
+            PacketType::Operator(_) => None,
+        }
+    }
+
+    pub fn operator(&self) -> Option<OperatorType> {
+        match &self.ptype {
+            PacketType::Literal(_) => None,
+            PacketType::Operator((op, _)) => Some(*op),
+        }
+    }
+
+    // Empty for literals.
+    pub fn subpackets(&self) -> &[Packet] {
+        match &self.ptype {
+            PacketType::Literal(_) => &[],
+            PacketType::Operator((_, packets)) => packets,
+        }
+    }
+
+    pub fn version_sum(&self) -> usize {
+        match &self.ptype {
+            PacketType::Literal(_) => self.version,
+            PacketType::Operator(sub) => {
+                self.version + sub.1.iter().map(|p| p.version_sum()).sum::<usize>()
+            }
+        }
+    }
+
+    // Evaluate the expression this packet represents.
+    pub fn value(&self) -> usize {
+        match &self.ptype {
+            PacketType::Literal(val) => bits_to_val(val),
+            PacketType::Operator((op, packets)) => match op {
+                OperatorType::Sum => packets.iter().map(|p| p.value()).sum(),
+                OperatorType::Product => packets.iter().fold(1, |acc, p| acc * p.value()),
+                // decode() makes sure these have at least one subpacket
+                OperatorType::Minimum => packets.iter().map(|p| p.value()).min().unwrap_or(0),
+                OperatorType::Maximum => packets.iter().map(|p| p.value()).max().unwrap_or(0),
+                OperatorType::GreaterThan => (packets[0].value() > packets[1].value()) as usize,
+                OperatorType::LessThan => (packets[0].value() < packets[1].value()) as usize,
+                OperatorType::EqualTo => (packets[0].value() == packets[1].value()) as usize,
+            },
+        }
+    }
+}
+
+// A whole BITS transmission, given in hex.
+impl FromStr for Packet {
+    type Err = PacketParseError;
+
+    fn from_str(buf: &str) -> Result<Self, Self::Err> {
+        Packet::new(hex_to_bits(buf)?)
+    }
+}
+
+// Append the low `count` bits of `value`, most significant first.
+fn push_bits(bits: &mut Vec<usize>, value: usize, count: usize) {
+    bits.extend((0..count).rev().map(|n| value >> n & 1));
+}
+
+fn push_literal(bits: &mut Vec<usize>, version: usize, value: usize) {
+    push_bits(bits, version, 3);
+    push_bits(bits, 4, 3);
+
+    let groups = ((usize::BITS - value.leading_zeros()) as usize)
+        .div_ceil(4)
+        .max(1);
+    for n in (0..groups).rev() {
+        push_bits(bits, (n > 0) as usize, 1);
+        push_bits(bits, value >> (n * 4) & 0xf, 4);
+    }
+}
+
+// `total` split into `parts` random pieces, none of them empty.
+fn split(rng: &mut Rng, total: usize, parts: usize) -> Vec<usize> {
+    let mut pieces = vec![1; parts];
+    for _ in parts..total {
+        pieces[rng.below(parts)] += 1;
+    }
+    pieces
+}
+
+// The bits for a random packet made of `packets` packets or a few less.
+// Products only ever multiply a handful of small literals, so that nothing
+// overflows working out the value.
+fn random_packet(rng: &mut Rng, packets: usize) -> Vec<usize> {
+    let mut bits = vec![];
+    let version = rng.below(8);
+
+    if packets <= 1 {
+        push_literal(&mut bits, version, rng.below(1 << 20));
+        return bits;
+    }
+
+    // Comparisons need room for two operands.
+    let type_id = match packets {
+        2 => *rng.choose(&[0, 1, 2, 3]),
+        _ => *rng.choose(&[0, 1, 2, 3, 5, 6, 7]),
+    };
+    let operands: Vec<Vec<usize>> = match type_id {
+        1 => (0..(packets - 1).min(4))
+            .map(|_| {
+                let mut literal = vec![];
+                push_literal(&mut literal, rng.below(8), rng.below(16));
+                literal
+            })
+            .collect(),
+        _ => {
+            let count = match type_id {
+                5..=7 => 2,
+                _ => rng.between(1, (packets as i64 - 1).min(5)) as usize,
+            };
+            split(rng, packets - 1, count)
+                .into_iter()
+                .map(|packets| random_packet(rng, packets))
+                .collect()
+        }
+    };
+
+    push_bits(&mut bits, version, 3);
+    push_bits(&mut bits, type_id, 3);
+    let length: usize = operands.iter().map(|operand| operand.len()).sum();
+    if length < 1 << 15 && rng.chance(1, 2) {
+        push_bits(&mut bits, 0, 1);
+        push_bits(&mut bits, length, 15);
+    } else {
+        push_bits(&mut bits, 1, 1);
+        push_bits(&mut bits, operands.len(), 11);
+    }
+    bits.extend(operands.into_iter().flatten());
+
+    bits
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: usize = 16;
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: "8A004A801A8002F478",
+            part1: Some("16"),
+            part2: None,
+        },
+        Example {
+            input: "620080001611562C8802118E34",
+            part1: Some("12"),
+            part2: None,
+        },
+        Example {
+            input: "C0015000016115A2E0802F182340",
+            part1: Some("23"),
+            part2: None,
+        },
+        Example {
+            input: "A0016C880162017C3686B18A3D4780",
+            part1: Some("31"),
+            part2: None,
+        },
+        Example {
+            input: "C200B40A82",
+            part1: None,
+            part2: Some("3"),
+        },
+        Example {
+            input: "04005AC33890",
+            part1: None,
+            part2: Some("54"),
+        },
+        Example {
+            input: "880086C3E88112",
+            part1: None,
+            part2: Some("7"),
+        },
+        Example {
+            input: "CE00C43D881120",
+            part1: None,
+            part2: Some("9"),
+        },
+        Example {
+            input: "D8005AC2A8F0",
+            part1: None,
+            part2: Some("1"),
+        },
+        Example {
+            input: "F600BC2D8F",
+            part1: None,
+            part2: Some("0"),
+        },
+        Example {
+            input: "9C005AC2F8F0",
+            part1: None,
+            part2: Some("0"),
+        },
+        Example {
+            input: "9C0141080250320F1802104A08",
+            part1: None,
+            part2: Some("1"),
+        },
+    ];
+
+    type Input = Packet;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input, Box<dyn std::error::Error>> {
+        let bits = parse(lines)?;
+
+        Ok(Packet::new(bits).map_err(|e| Located::new(1, e))?)
+    }
+
+    fn part1(packet: &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+        Ok(packet.version_sum())
+    }
+
+    fn part2(packet: &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+        Ok(packet.value())
+    }
+
+    // A transmission of about `size` packets.
+    fn generate(rng: &mut Rng, size: Option<usize>) -> Vec<String> {
+        let mut bits = random_packet(rng, size.unwrap_or(250));
+        // Padded out to a whole number of hex digits.
+        bits.resize(bits.len().div_ceil(4) * 4, 0);
+
+        let hex: String = bits
+            .chunks(4)
+            .map(|digit| format!("{:X}", bits_to_val(digit)))
+            .collect();
+
+        vec![hex]
+    }
+}
+
+pub fn run(source: Option<Source>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    common::run::<Day16>(source)
+}
+
+common::solution_tests!(Day16);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn from_str_test() {
+        let packet = "8A004A801A8002F478".parse::<Packet>().unwrap();
+
+        assert!(packet.version() == 4);
+        assert!(packet.operator() == Some(OperatorType::Minimum));
+        assert!(packet.subpackets()[0].subpackets()[0].subpackets()[0].literal() == Some(15));
+        assert!("D2FE28".parse::<Packet>().unwrap().literal() == Some(2021));
+        assert!("D2FE2X".parse::<Packet>().err() == Some(PacketParseError::Digit(6, 'X')));
+        assert!("".parse::<Packet>().is_err());
+        // a literal that stops before its last group
+        assert!("D2FE".parse::<Packet>().err() == Some(PacketParseError::Truncated(16)));
+        // a less-than with only one subpacket
+        assert!(
+            "DA004408".parse::<Packet>().err()
+                == Some(PacketParseError::Operands(OperatorType::LessThan, 1))
+        );
+    }
+
+    #[test]
+    fn example_1() {
+        let bits = parse(vec!["D2FE28".to_string()]).unwrap();
+
+        let packet = Packet::new(bits).unwrap();
+
+        dbg!(&packet);
+        match &packet.ptype {
+            PacketType::Literal(val) => assert!(bits_to_val(val) == 2021),
+            PacketType::Operator(_) => panic!(),
+        }
+    }
+
+    #[test]
+    fn example_2() {
+        let bits = parse(vec!["38006F45291200".to_string()]).unwrap();
+
+        let packet = Packet::new(bits).unwrap();
+
+        dbg!(&packet);
+    }
+
+    #[test]
+    fn example_3() {
+        let bits = parse(vec!["8A004A801A8002F478".to_string()]).unwrap();
+
+        let packet = Packet::new(bits).unwrap();
+
+        if packet.version != 4 {
+            panic!();
+        }
+        match &packet.ptype {
+            PacketType::Literal(_) => panic!(),
+            PacketType::Operator(subpackets) => {
+                let sub = subpackets.1.first().unwrap();
+                if sub.version != 1 {
+                    panic!();
+                }
+                match &sub.ptype {
+                    PacketType::Literal(_) => panic!(),
+                    PacketType::Operator(subpackets) => {
+                        let sub = subpackets.1.first().unwrap();
+                        if sub.version != 5 {
+                            panic!();
+                        }
+                        match &sub.ptype {
+                            PacketType::Operator(subpackets) => {
+                                let sub = subpackets.1.first().unwrap();
+                                if sub.version != 6 {
+                                    panic!();
+                                }
+                                match &sub.ptype {
+                                    PacketType::Literal(val) => {
+                                        println!("{}", bits_to_val(val));
+                                    }
+                                    PacketType::Operator(_) => panic!(),
+                                }
+                            }
+                            PacketType::Literal(_) => panic!(),
+                        }
+                    }
+                }
+            }
+        }
+
+        dbg!(&packet);
+        assert!(packet.version_sum() == 16);
+    }
+
+    #[test]
+    fn example_4() {
+        let bits = parse(vec!["620080001611562C8802118E34".to_string()]).unwrap();
+
+        let packet = Packet::new(bits).unwrap();
+
+        println!("{:?}", packet);
+
+        assert!(packet.version_sum() == 12);
+    }
+
+    #[test]
+    fn example_5() {
+        let bits = parse(vec!["C0015000016115A2E0802F182340".to_string()]).unwrap();
+
+        let packet = Packet::new(bits).unwrap();
+
+        println!("{:?}", packet);
+
+        assert!(packet.version_sum() == 23);
+    }
+
+    #[test]
+    fn example_6() {
+        let bits = parse(vec!["A0016C880162017C3686B18A3D4780".to_string()]).unwrap();
+
+        let packet = Packet::new(bits).unwrap();
+
+        println!("{:?}", packet);
+
+        assert!(packet.version_sum() == 31);
+    }
+
+    #[test]
+    fn example_7() {
+        let bits = parse(vec!["C200B40A82".to_string()]).unwrap();
+
+        let packet = Packet::new(bits).unwrap();
+
+        assert!(packet.value() == 3);
+    }
+
+    #[test]
+    fn example_8() {
+        let bits = parse(vec!["04005AC33890".to_string()]).unwrap();
+
+        let packet = Packet::new(bits).unwrap();
+
+        assert!(packet.value() == 54);
+    }
+
+    #[test]
+    fn example_9() {
+        let bits = parse(vec!["880086C3E88112".to_string()]).unwrap();
+
+        let packet = Packet::new(bits).unwrap();
+
+        assert!(packet.value() == 7);
+    }
+
+    #[test]
+    fn example_10() {
+        let bits = parse(vec!["CE00C43D881120".to_string()]).unwrap();
+
+        let packet = Packet::new(bits).unwrap();
+
+        assert!(packet.value() == 9);
+    }
+
+    #[test]
+    fn example_11() {
+        let bits = parse(vec!["D8005AC2A8F0".to_string()]).unwrap();
+
+        let packet = Packet::new(bits).unwrap();
+
+        assert!(packet.value() == 1);
+    }
+
+    #[test]
+    fn example_12() {
+        let bits = parse(vec!["F600BC2D8F".to_string()]).unwrap();
+
+        let packet = Packet::new(bits).unwrap();
+
+        assert!(packet.value() == 0);
+    }
+
+    #[test]
+    fn example_13() {
+        let bits = parse(vec!["9C005AC2F8F0".to_string()]).unwrap();
+
+        let packet = Packet::new(bits).unwrap();
+
+        assert!(packet.value() == 0);
+    }
+
+    #[test]
+    fn example_14() {
+        let bits = parse(vec!["9C0141080250320F1802104A08".to_string()]).unwrap();
+
+        let packet = Packet::new(bits).unwrap();
+
+        assert!(packet.value() == 1);
+    }
+}
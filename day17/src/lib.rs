@@ -0,0 +1,248 @@
+use common::{Example, Located, Rng, Solution, Source};
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq)]
+pub struct Range {
+    x_start: isize,
+    x_end: isize,
+    y_start: isize,
+    y_end: isize,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RangeParseError {
+    Prefix,
+    Axis(char),
+    // column, and what was there
+    Bound(usize, String),
+    Position,
+}
+
+impl std::error::Error for RangeParseError {}
+
+impl std::fmt::Display for RangeParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RangeParseError::Prefix => write!(f, "expected \"target area: \""),
+            RangeParseError::Axis(axis) => write!(f, "expected a range like {}=1..2", axis),
+            RangeParseError::Bound(column, bound) => {
+                write!(f, "\"{}\" at column {} isn't a number", bound, column)
+            }
+            RangeParseError::Position => {
+                write!(f, "target has to be ahead of the launcher, and below it")
+            }
+        }
+    }
+}
+
+// "target area: x=20..30, y=-10..-5"
+impl FromStr for Range {
+    type Err = RangeParseError;
+
+    fn from_str(buf: &str) -> Result<Self, Self::Err> {
+        let ranges = buf
+            .trim()
+            .strip_prefix("target area: ")
+            .ok_or(Self::Err::Prefix)?;
+        let (x, y) = ranges.split_once(", ").ok_or(Self::Err::Axis('y'))?;
+
+        let parse_axis = |range: &str, axis: char| {
+            let (start, end) = range
+                .strip_prefix(axis)
+                .and_then(|r| r.strip_prefix('='))
+                .and_then(|r| r.split_once(".."))
+                .ok_or(Self::Err::Axis(axis))?;
+            let parse_bound = |b: &str| {
+                b.parse::<isize>()
+                    .map_err(|_| Self::Err::Bound(common::parse::column(buf, b), b.to_string()))
+            };
+            let (start, end) = (parse_bound(start)?, parse_bound(end)?);
+            Ok((start.min(end), start.max(end)))
+        };
+
+        let (x_start, x_end) = parse_axis(x, 'x')?;
+        let (y_start, y_end) = parse_axis(y, 'y')?;
+
+        // launch() only searches for shots forward and down.
+        if x_start <= 0 || y_end >= 0 {
+            return Err(Self::Err::Position);
+        }
+
+        Ok(Range {
+            x_start,
+            x_end,
+            y_start,
+            y_end,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct Probe {
+    x: isize,
+    y: isize,
+    x_vel: isize,
+    y_vel: isize,
+    peak: isize,
+}
+
+impl Probe {
+    // Does the probe hit its target?
+    fn fire(&mut self, target: &Range) -> bool {
+        while self.x < target.x_end && self.y > target.y_start {
+            self.step();
+            if self.x >= target.x_start
+                && self.x <= target.x_end
+                && self.y >= target.y_start
+                && self.y <= target.y_end
+            {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn step(&mut self) {
+        self.x += self.x_vel;
+        self.y += self.y_vel;
+        if self.peak < self.y {
+            self.peak = self.y;
+        }
+        if self.x_vel > 0 {
+            self.x_vel -= 1;
+        } else if self.x_vel < 0 {
+            self.x_vel += 1;
+        }
+        self.y_vel -= 1;
+    }
+}
+
+fn find_probes_for_y(y: isize, range: &Range) -> (isize, usize) {
+    let mut x_vel = 0;
+    let mut probe;
+
+    let mut peak = 0;
+    let mut count = 0;
+
+    while x_vel < range.x_end {
+        x_vel += 1;
+        probe = Probe {
+            x: 0,
+            y: 0,
+            x_vel,
+            y_vel: y,
+            peak: 0,
+        };
+        if probe.fire(range) {
+            count += 1;
+            if probe.peak > peak {
+                peak = probe.peak;
+            }
+        }
+    }
+
+    (peak, count)
+}
+
+// Returns the highest peak of any probe that hits the target, and how many
+// initial velocities hit it.  Any faster downwards and the first step
+// overshoots the bottom of the target.  Going up, the probe comes back down
+// through y=0 going one faster than it went up, so the same applies.
+pub fn launch(range: &Range) -> (isize, usize) {
+    let mut best_peak = -1;
+    let mut count = 0;
+
+    for y_vel in range.y_start..-range.y_start {
+        let (peak, new_count) = find_probes_for_y(y_vel, range);
+        if peak > best_peak {
+            best_peak = peak;
+        }
+        count += new_count;
+    }
+
+    (best_peak, count)
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: usize = 17;
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: "target area: x=20..30, y=-10..-5",
+        part1: Some("45"),
+        part2: Some("112"),
+    }];
+
+    type Input = Range;
+    type Part1 = isize;
+    type Part2 = usize;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input, Box<dyn std::error::Error>> {
+        let line = lines.first().ok_or(RangeParseError::Prefix)?;
+
+        Ok(line.parse::<Range>().map_err(|e| Located::new(1, e))?)
+    }
+
+    fn part1(range: &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+        Ok(launch(range).0)
+    }
+
+    fn part2(range: &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+        Ok(launch(range).1)
+    }
+
+    // A target about `size` away, below the probe.
+    fn generate(rng: &mut Rng, size: Option<usize>) -> Vec<String> {
+        let size = size.unwrap_or(250).max(4) as i64;
+        let x_start = rng.between(size / 4, size);
+        let x_end = x_start + rng.between(0, size / 5);
+        let y_end = -rng.between(size / 5, size / 2).max(1);
+        let y_start = y_end - rng.between(0, size / 5);
+
+        vec![format!(
+            "target area: x={}..{}, y={}..{}",
+            x_start, x_end, y_start, y_end
+        )]
+    }
+}
+
+pub fn run(source: Option<Source>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    common::run::<Day17>(source)
+}
+
+common::solution_tests!(Day17);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        let range = "target area: x=20..30, y=-10..-5".parse::<Range>().unwrap();
+
+        assert!(
+            range
+                == Range {
+                    x_start: 20,
+                    x_end: 30,
+                    y_start: -10,
+                    y_end: -5,
+                }
+        );
+        assert!(launch(&range) == (45, 112));
+        // Deeper than any fixed search would have guessed.
+        let deep = "target area: x=20..30, y=-150..-100"
+            .parse::<Range>()
+            .unwrap();
+        assert!(launch(&deep) == (11175, 1092));
+        assert!("target area: x=20..30".parse::<Range>().is_err());
+        assert!(
+            "target area: x=20..30, y=-10..five".parse::<Range>()
+                == Err(RangeParseError::Bound(31, "five".to_string()))
+        );
+        assert!(
+            "target area: x=20..30, y=5..10".parse::<Range>() == Err(RangeParseError::Position)
+        );
+    }
+}
@@ -0,0 +1,350 @@
+use common::{Example, Located, Rng, Solution, Source};
+
+#[derive(Debug, PartialEq)]
+pub enum SnailfishError {
+    Empty,
+    // column, and what was there
+    Unexpected(usize, char),
+    End,
+}
+
+impl std::error::Error for SnailfishError {}
+
+impl std::fmt::Display for SnailfishError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SnailfishError::Empty => write!(f, "no snailfish numbers to add"),
+            SnailfishError::Unexpected(column, c) => {
+                write!(f, "didn't expect \"{}\" at column {}", c, column)
+            }
+            SnailfishError::End => write!(f, "line ends before the number does"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Element {
+    Value(usize),
+    Pair(Vec<Element>),
+}
+
+#[derive(Debug)]
+struct Explosion {
+    x: usize,
+    y: usize,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+impl Element {
+    fn get_left(&self) -> usize {
+        match self {
+            Element::Value(v) => *v,
+            Element::Pair(p) => p[0].get_left(),
+        }
+    }
+
+    fn add_left(&mut self, val: usize) {
+        match self {
+            Element::Value(v) => *self = Element::Value(*v + val),
+            Element::Pair(p) => p[0].add_left(val),
+        }
+    }
+
+    fn get_right(&self) -> usize {
+        match self {
+            Element::Value(v) => *v,
+            Element::Pair(p) => p[1].get_right(),
+        }
+    }
+
+    fn add_right(&mut self, val: usize) {
+        match self {
+            Element::Value(v) => *self = Element::Value(*v + val),
+            Element::Pair(p) => p[1].add_right(val),
+        }
+    }
+
+    fn explode(&mut self, depth: usize) -> Option<Explosion> {
+        match self {
+            Element::Value(_) => None,
+            Element::Pair(p) => {
+                if depth == 4 {
+                    let x = self.get_left();
+                    let y = self.get_right();
+
+                    *self = Element::Value(0);
+
+                    Some(Explosion {
+                        x,
+                        y,
+                        left: None,
+                        right: None,
+                    })
+                } else {
+                    if let Some(mut e) = p[0].explode(depth + 1) {
+                        if e.right.is_none() {
+                            e.right = Some(p[1].get_left());
+                            p[1].add_left(e.y);
+                        }
+                        Some(e)
+                    } else if let Some(mut e) = p[1].explode(depth + 1) {
+                        if e.left.is_none() {
+                            e.left = Some(p[0].get_right());
+                            p[0].add_right(e.x);
+                        }
+                        Some(e)
+                    } else {
+                        None
+                    }
+                }
+            }
+        }
+    }
+
+    fn split(&mut self) -> bool {
+        match self {
+            Element::Value(v) => {
+                if *v >= 10 {
+                    let x = *v / 2;
+                    let y = if x * 2 == *v { x } else { x + 1 };
+                    *self = Element::Pair(vec![Element::Value(x), Element::Value(y)]);
+                    true
+                } else {
+                    false
+                }
+            }
+            Element::Pair(p) => {
+                if p[0].split() {
+                    return true;
+                }
+                p[1].split()
+            }
+        }
+    }
+
+    fn magnitude(&self) -> usize {
+        match self {
+            Element::Value(v) => *v,
+            Element::Pair(p) => 3 * p[0].magnitude() + 2 * p[1].magnitude(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Pair {
+    x: Element,
+    y: Element,
+}
+
+impl Pair {
+    fn new(line: &[char]) -> Pair {
+        let mut opens = 0;
+        let mut read = vec![];
+        let mut left = vec![];
+
+        // First pass
+        for &c in line.iter().skip(1) {
+            read.push(c);
+            if c == '[' {
+                opens += 1;
+            } else if c == ']' {
+                opens -= 1;
+            } else if c == ',' && opens == 0 {
+                read.pop();
+                left = read;
+                read = vec![];
+            }
+        }
+        read.pop();
+        let right = read;
+
+        let x = if left.len() == 1 {
+            Element::Value(left[0].to_digit(10).unwrap() as usize)
+        } else {
+            let pair = Pair::new(&left);
+            Element::Pair(vec![pair.x, pair.y])
+        };
+
+        let y = if right.len() == 1 {
+            Element::Value(right[0].to_digit(10).unwrap() as usize)
+        } else {
+            let pair = Pair::new(&right);
+            Element::Pair(vec![pair.x, pair.y])
+        };
+
+        Pair { x, y }
+    }
+
+    fn explode(&mut self) -> bool {
+        if let Some(e) = self.x.explode(1) {
+            if e.right.is_none() {
+                self.y.add_left(e.y);
+            }
+            true
+        } else if let Some(e) = self.y.explode(1) {
+            if e.left.is_none() {
+                self.x.add_right(e.x);
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    fn split(&mut self) -> bool {
+        self.x.split() || self.y.split()
+    }
+
+    fn to_element(&self) -> Element {
+        Element::Pair(vec![self.x.clone(), self.y.clone()])
+    }
+
+    pub fn add(&self, other: &Pair) -> Pair {
+        let mut pair = Pair {
+            x: self.to_element(),
+            y: other.to_element(),
+        };
+
+        while pair.explode() || pair.split() {}
+
+        pair
+    }
+
+    pub fn magnitude(&self) -> usize {
+        3 * self.x.magnitude() + 2 * self.y.magnitude()
+    }
+}
+
+fn expect(line: &[char], at: usize, want: char) -> Result<usize, SnailfishError> {
+    match line.get(at) {
+        Some(&c) if c == want => Ok(at + 1),
+        Some(&c) => Err(SnailfishError::Unexpected(at + 1, c)),
+        None => Err(SnailfishError::End),
+    }
+}
+
+// Check the element starting at `at` is a single digit or a pair of elements,
+// and return where it ends.
+fn check_element(line: &[char], at: usize, pair_only: bool) -> Result<usize, SnailfishError> {
+    match line.get(at) {
+        Some(c) if c.is_ascii_digit() && !pair_only => Ok(at + 1),
+        Some('[') => {
+            let at = check_element(line, at + 1, false)?;
+            let at = expect(line, at, ',')?;
+            let at = check_element(line, at, false)?;
+            expect(line, at, ']')
+        }
+        Some(&c) => Err(SnailfishError::Unexpected(at + 1, c)),
+        None => Err(SnailfishError::End),
+    }
+}
+
+// Pair::new assumes it's given a well formed pair, so make sure of that first.
+fn parse_pair(line: &str) -> Result<Pair, SnailfishError> {
+    let line = line.chars().collect::<Vec<char>>();
+
+    let end = check_element(&line, 0, true)?;
+    if let Some(&c) = line.get(end) {
+        return Err(SnailfishError::Unexpected(end + 1, c));
+    }
+
+    Ok(Pair::new(&line))
+}
+
+fn parse(lines: &[String]) -> Result<Vec<Pair>, Box<dyn std::error::Error>> {
+    let mut pairs = vec![];
+
+    for (n, line) in lines.iter().enumerate() {
+        pairs.push(parse_pair(line).map_err(|e| Located::new(n + 1, e))?);
+    }
+
+    if pairs.is_empty() {
+        return Err(SnailfishError::Empty.into());
+    }
+    Ok(pairs)
+}
+
+// A random snailfish number, already reduced: nothing is nested inside four
+// pairs and every regular number is below 10.  `depth` is how many pairs
+// it's already inside.
+fn random_number(rng: &mut Rng, depth: usize) -> String {
+    if depth == 0 || (depth < 4 && rng.chance(2, 3)) {
+        format!(
+            "[{},{}]",
+            random_number(rng, depth + 1),
+            random_number(rng, depth + 1)
+        )
+    } else {
+        rng.below(10).to_string()
+    }
+}
+
+const EXAMPLE: &str = "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
+[[[5,[2,8]],4],[5,[[9,9],0]]]
+[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
+[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]
+[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]
+[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]
+[[[[5,4],[7,7]],8],[[8,3],8]]
+[[9,3],[[9,9],[6,[4,9]]]]
+[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
+[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]";
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: usize = 18;
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("4140"),
+        part2: Some("3993"),
+    }];
+
+    type Input = Vec<Pair>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input, Box<dyn std::error::Error>> {
+        parse(&lines)
+    }
+
+    fn part1(pairs: &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+        let result = pairs
+            .clone()
+            .into_iter()
+            .reduce(|prev, next| prev.add(&next))
+            .ok_or(SnailfishError::Empty)?;
+
+        Ok(result.magnitude())
+    }
+
+    fn part2(pairs: &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+        Ok(pairs
+            .clone()
+            .into_iter()
+            .filter_map(|p1| {
+                pairs
+                    .clone()
+                    .into_iter()
+                    .map(|p2| p1.add(&p2).magnitude())
+                    .max()
+            })
+            .max()
+            .ok_or(SnailfishError::Empty)?)
+    }
+
+    // `size` numbers to add up.
+    fn generate(rng: &mut Rng, size: Option<usize>) -> Vec<String> {
+        (0..size.unwrap_or(100))
+            .map(|_| random_number(rng, 0))
+            .collect()
+    }
+}
+
+pub fn run(source: Option<Source>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    common::run::<Day18>(source)
+}
+
+common::solution_tests!(Day18);
@@ -0,0 +1,536 @@
+use common::progress::OutOfBudget;
+use common::{Example, Located, Progress, Rng, Solution, Source};
+use std::collections::HashSet;
+
+#[derive(Debug, PartialEq)]
+pub enum ScannerError {
+    Empty,
+    // column, and what was there
+    Coordinate(usize, String),
+    // how many coordinates there were
+    Dimensions(usize),
+}
+
+impl std::error::Error for ScannerError {}
+
+impl std::fmt::Display for ScannerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ScannerError::Empty => write!(f, "no scanners to align"),
+            ScannerError::Coordinate(column, s) => {
+                write!(f, "\"{}\" at column {} isn't a coordinate", s, column)
+            }
+            ScannerError::Dimensions(n) => {
+                write!(f, "beacon has {} coordinates, expected x,y,z", n)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AlignError {
+    // the scanners (counting from 0, like the input) that don't share 12
+    // beacons with any of the others
+    Unaligned(Vec<usize>),
+    OutOfBudget(OutOfBudget),
+}
+
+impl From<OutOfBudget> for AlignError {
+    fn from(e: OutOfBudget) -> AlignError {
+        AlignError::OutOfBudget(e)
+    }
+}
+
+impl std::error::Error for AlignError {}
+
+impl std::fmt::Display for AlignError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            AlignError::Unaligned(scanners) => {
+                let scanners: Vec<String> = scanners.iter().map(|n| n.to_string()).collect();
+                write!(
+                    f,
+                    "scanner(s) {} don't overlap with the rest",
+                    scanners.join(", ")
+                )
+            }
+            AlignError::OutOfBudget(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, PartialOrd, Clone, Hash)]
+pub struct Position {
+    x: isize,
+    y: isize,
+    z: isize,
+}
+
+impl Eq for Position {}
+
+impl Position {
+    fn new(x: isize, y: isize, z: isize) -> Position {
+        Position { x, y, z }
+    }
+
+    fn add(&self, other: &Self) -> Position {
+        Position {
+            x: other.x + self.x,
+            y: other.y + self.y,
+            z: other.z + self.z,
+        }
+    }
+
+    fn sub(&self, other: &Self) -> Position {
+        Position {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z,
+        }
+    }
+
+    fn abs(&self) -> Position {
+        Position {
+            x: self.x.abs(),
+            y: self.y.abs(),
+            z: self.z.abs(),
+        }
+    }
+
+    fn sum(&self) -> isize {
+        self.x + self.y + self.z
+    }
+
+    fn permute(&self, val: usize) -> Position {
+        let mut permutations = vec![];
+        // This is double the amount of permutations but I have no clue
+        // which ones are unused.
+        for x in [1, -1] {
+            for y in [1, -1] {
+                for z in [1, -1] {
+                    let x = self.x * x;
+                    let y = self.y * y;
+                    let z = self.z * z;
+                    permutations.push(Position::new(x, y, z));
+                    permutations.push(Position::new(x, z, y));
+                    permutations.push(Position::new(y, z, x));
+                    permutations.push(Position::new(y, x, z));
+                    permutations.push(Position::new(z, x, y));
+                    permutations.push(Position::new(z, y, x));
+                }
+            }
+        }
+        permutations[val].clone()
+    }
+}
+
+fn parse_position(line: &str) -> Result<Position, ScannerError> {
+    let values = line
+        .split(",")
+        .map(|n| {
+            n.parse::<isize>().map_err(|_| {
+                ScannerError::Coordinate(common::parse::column(line, n), n.to_string())
+            })
+        })
+        .collect::<Result<Vec<isize>, ScannerError>>()?;
+
+    match values[..] {
+        [x, y, z] => Ok(Position { x, y, z }),
+        _ => Err(ScannerError::Dimensions(values.len())),
+    }
+}
+
+fn parse(lines: Vec<String>) -> Result<Vec<Vec<Position>>, Box<dyn std::error::Error>> {
+    let mut scanners = vec![];
+    let mut positions = vec![];
+
+    for (n, line) in lines.iter().enumerate() {
+        if line.starts_with("---") {
+            continue;
+        } else if line.is_empty() {
+            // (an extra blank line isn't a scanner that sees nothing)
+            if !positions.is_empty() {
+                scanners.push(positions);
+            }
+            positions = vec![];
+        } else {
+            positions.push(parse_position(line).map_err(|e| Located::new(n + 1, e))?);
+        }
+    }
+
+    if !positions.is_empty() {
+        scanners.push(positions);
+    }
+
+    if scanners.is_empty() {
+        return Err(ScannerError::Empty.into());
+    }
+    Ok(scanners)
+}
+
+// Never used HashSet before, it's a bit faster and saves checking for duplicates
+fn merge(probes: &mut HashSet<Position>, other: &[Position]) -> Option<Position> {
+    for i in 0..48 {
+        let adjusted = other
+            .iter()
+            .map(|v| v.permute(i))
+            .collect::<HashSet<Position>>();
+        let distances: Vec<Position> = probes
+            .iter()
+            .flat_map(|x| adjusted.iter().map(|y| (x.clone(), y.clone())))
+            .map(|(x, y)| x.sub(&y))
+            .collect();
+
+        for offset in distances {
+            let adjusted = adjusted
+                .iter()
+                .map(|x| x.add(&offset))
+                .collect::<Vec<Position>>();
+
+            if adjusted.iter().filter(|x| probes.contains(x)).count() >= 12 {
+                probes.extend(adjusted);
+                return Some(offset);
+            }
+        }
+    }
+
+    None
+}
+
+// Merge every scanner's beacons into the first scanner's frame of reference.
+// Returns the set of beacons, and the position of each scanner after the first.
+pub fn align(scanners: &[Vec<Position>]) -> Result<(HashSet<Position>, Vec<Position>), AlignError> {
+    // Keeping the numbers they came with, to say which ones never fit.
+    let mut scanners: Vec<(usize, Vec<Position>)> = scanners.iter().cloned().enumerate().collect();
+    let mut probes = HashSet::new();
+
+    probes.extend(scanners.remove(0).1);
+
+    let mut distances = vec![];
+    let mut progress =
+        Progress::start("aligning scanners", "scanners merged").with_total(scanners.len() as u64);
+
+    while !scanners.is_empty() {
+        let before = scanners.len();
+
+        for i in (0..scanners.len()).rev() {
+            if let Some(distance) = merge(&mut probes, &scanners[i].1) {
+                distances.push(distance);
+                scanners.remove(i);
+                progress.step()?;
+            } else {
+                progress.check()?;
+            }
+        }
+
+        // If nothing fit this time round, nothing ever will.
+        if scanners.len() == before {
+            let mut unaligned: Vec<usize> = scanners.iter().map(|(n, _)| *n).collect();
+            unaligned.sort();
+            return Err(AlignError::Unaligned(unaligned));
+        }
+    }
+
+    Ok((probes, distances))
+}
+
+// One of the 24 ways a scanner can face: the axes in any order and either
+// way round, so long as it doesn't end up as its mirror image.
+fn random_facing(rng: &mut Rng) -> impl Fn(&Position) -> Position {
+    // Swapping two axes mirrors it, and so does flipping one.
+    let orders = [
+        ([0, 1, 2], 1),
+        ([1, 2, 0], 1),
+        ([2, 0, 1], 1),
+        ([0, 2, 1], -1),
+        ([2, 1, 0], -1),
+        ([1, 0, 2], -1),
+    ];
+    let (order, handedness) = *rng.choose(&orders);
+    let mut signs = [1, 1, 1].map(|sign| if rng.chance(1, 2) { sign } else { -sign });
+    if signs.iter().product::<isize>() != handedness {
+        signs[2] = -signs[2];
+    }
+
+    move |position| {
+        let axes = [position.x, position.y, position.z];
+        let [x, y, z] = [0, 1, 2].map(|n| axes[order[n]] * signs[n]);
+        Position::new(x, y, z)
+    }
+}
+
+// Somewhere every scanner in `scanners` can see.
+fn random_beacon(rng: &mut Rng, scanners: &[Position]) -> Position {
+    let range = |axis: fn(&Position) -> isize| {
+        let min = scanners.iter().map(axis).max().unwrap_or(0) - 1000;
+        let max = scanners.iter().map(axis).min().unwrap_or(0) + 1000;
+        (min as i64, max as i64)
+    };
+    let ((x_min, x_max), (y_min, y_max), (z_min, z_max)) =
+        (range(|p| p.x), range(|p| p.y), range(|p| p.z));
+
+    Position::new(
+        rng.between(x_min, x_max) as isize,
+        rng.between(y_min, y_max) as isize,
+        rng.between(z_min, z_max) as isize,
+    )
+}
+
+const EXAMPLE: &str = "--- scanner 0 ---
+404,-588,-901
+528,-643,409
+-838,591,734
+390,-675,-793
+-537,-823,-458
+-485,-357,347
+-345,-311,381
+-661,-816,-575
+-876,649,763
+-618,-824,-621
+553,345,-567
+474,580,667
+-447,-329,318
+-584,868,-557
+544,-627,-890
+564,392,-477
+455,729,728
+-892,524,684
+-689,845,-530
+423,-701,434
+7,-33,-71
+630,319,-379
+443,580,662
+-789,900,-551
+459,-707,401
+
+--- scanner 1 ---
+686,422,578
+605,423,415
+515,917,-361
+-336,658,858
+95,138,22
+-476,619,847
+-340,-569,-846
+567,-361,727
+-460,603,-452
+669,-402,600
+729,430,532
+-500,-761,534
+-322,571,750
+-466,-666,-811
+-429,-592,574
+-355,545,-477
+703,-491,-529
+-328,-685,520
+413,935,-424
+-391,539,-444
+586,-435,557
+-364,-763,-893
+807,-499,-711
+755,-354,-619
+553,889,-390
+
+--- scanner 2 ---
+649,640,665
+682,-795,504
+-784,533,-524
+-644,584,-595
+-588,-843,648
+-30,6,44
+-674,560,763
+500,723,-460
+609,671,-379
+-555,-800,653
+-675,-892,-343
+697,-426,-610
+578,704,681
+493,664,-388
+-671,-858,530
+-667,343,800
+571,-461,-707
+-138,-166,112
+-889,563,-600
+646,-828,498
+640,759,510
+-630,509,768
+-681,-892,-333
+673,-379,-804
+-742,-814,-386
+577,-820,562
+
+--- scanner 3 ---
+-589,542,597
+605,-692,669
+-500,565,-823
+-660,373,557
+-458,-679,-417
+-488,449,543
+-626,468,-788
+338,-750,-386
+528,-832,-391
+562,-778,733
+-938,-730,414
+543,643,-506
+-524,371,-870
+407,773,750
+-104,29,83
+378,-903,-323
+-778,-728,485
+426,699,580
+-438,-605,-362
+-469,-447,-387
+509,732,623
+647,635,-688
+-868,-804,481
+614,-800,639
+595,780,-596
+
+--- scanner 4 ---
+727,592,562
+-293,-554,779
+441,611,-461
+-714,465,-776
+-743,427,-804
+-660,-479,-426
+832,-632,460
+927,-485,-438
+408,393,-506
+466,436,-512
+110,16,151
+-258,-428,682
+-393,719,612
+-211,-452,876
+808,-476,-593
+-575,615,604
+-485,667,467
+-680,325,-822
+-627,-443,-432
+872,-547,-609
+833,512,582
+807,604,487
+839,-516,451
+891,-625,532
+-652,-548,-490
+30,-46,-14";
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: usize = 19;
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("79"),
+        part2: Some("3621"),
+    }];
+
+    type Input = Vec<Vec<Position>>;
+    type Part1 = usize;
+    type Part2 = isize;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input, Box<dyn std::error::Error>> {
+        parse(lines)
+    }
+
+    fn part1(scanners: &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+        let (probes, _) = align(scanners)?;
+
+        Ok(probes.len())
+    }
+
+    fn part2(scanners: &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+        let (_, distances) = align(scanners)?;
+
+        Ok(distances
+            .iter()
+            .filter_map(|x| distances.iter().map(|y| x.sub(y).abs().sum()).max())
+            .max()
+            .unwrap_or(0))
+    }
+
+    // `size` scanners, each one placed near one before it and sharing 12
+    // beacons with it, so they can all be pieced together.
+    fn generate(rng: &mut Rng, size: Option<usize>) -> Vec<String> {
+        let mut scanners = vec![Position::new(0, 0, 0)];
+        let mut beacons = HashSet::new();
+
+        for _ in 0..15 {
+            beacons.insert(random_beacon(rng, &scanners));
+        }
+        for _ in 1..size.unwrap_or(30) {
+            // Keeping them apart stops any one scanner seeing far more
+            // beacons than the puzzle's do.
+            let (near, scanner) = loop {
+                let near = rng.choose(&scanners).clone();
+                let offset = [0; 3].map(|_| rng.between(-1200, 1200) as isize);
+                let scanner = near.add(&Position::new(offset[0], offset[1], offset[2]));
+                let apart = |other: &Position| {
+                    let d = scanner.sub(other).abs();
+                    d.x.max(d.y).max(d.z) >= 1100
+                };
+                if scanners.iter().all(apart) {
+                    break (near, scanner);
+                }
+            };
+
+            let mut shared = HashSet::new();
+            while shared.len() < 12 {
+                shared.insert(random_beacon(rng, &[near.clone(), scanner.clone()]));
+            }
+            beacons.extend(shared);
+            for _ in 0..rng.below(10) {
+                beacons.insert(random_beacon(rng, std::slice::from_ref(&scanner)));
+            }
+            scanners.push(scanner);
+        }
+
+        let mut lines = vec![];
+        for (n, scanner) in scanners.iter().enumerate() {
+            let facing = random_facing(rng);
+            let mut seen: Vec<String> = beacons
+                .iter()
+                .map(|beacon| beacon.sub(scanner))
+                .filter(|relative| {
+                    [relative.x, relative.y, relative.z]
+                        .iter()
+                        .all(|d| d.abs() <= 1000)
+                })
+                .map(|relative| {
+                    let p = facing(&relative);
+                    format!("{},{},{}", p.x, p.y, p.z)
+                })
+                .collect();
+            // (a HashSet isn't in the same order every time)
+            seen.sort();
+            rng.shuffle(&mut seen);
+
+            if n > 0 {
+                lines.push(String::new());
+            }
+            lines.push(format!("--- scanner {} ---", n));
+            lines.extend(seen);
+        }
+
+        lines
+    }
+}
+
+pub fn run(source: Option<Source>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    common::run::<Day19>(source)
+}
+
+common::solution_tests!(Day19);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn unaligned_test() {
+        let mut lines: Vec<String> = EXAMPLE.lines().map(String::from).collect();
+        lines.extend(["", "--- scanner 5 ---", "1,2,3", "4,5,6"].map(String::from));
+        let scanners = parse(lines).unwrap();
+
+        assert!(align(&scanners) == Err(AlignError::Unaligned(vec![5])));
+    }
+}
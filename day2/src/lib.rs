@@ -0,0 +1,615 @@
+use common::{Example, Located, Rng, Solution, Source};
+
+#[derive(Debug, PartialEq)]
+pub enum CommandParseError {
+    Empty,
+    Verb(String),
+    // column, and what was there
+    Value(usize, String),
+    // what was missing, and what it was missing from
+    Missing(&'static str, String),
+    // a repeat whose block is never closed
+    Unclosed,
+    // a } with no repeat to close
+    Unopened(usize),
+    // column of a repeat that would run for longer than MAX_STEPS
+    TooLong(usize),
+}
+
+impl std::error::Error for CommandParseError {}
+
+impl std::fmt::Display for CommandParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CommandParseError::Empty => write!(f, "no commands to follow"),
+            CommandParseError::Verb(s) => write!(f, "\"{}\" isn't a command", s),
+            CommandParseError::Value(column, s) => {
+                write!(f, "failed to parse value \"{}\" at column {}", s, column)
+            }
+            CommandParseError::Missing(what, s) => write!(f, "\"{}\" needs {}", s, what),
+            CommandParseError::Unclosed => write!(f, "repeat is missing its closing }}"),
+            CommandParseError::Unopened(column) => {
+                write!(f, "}} at column {} doesn't close anything", column)
+            }
+            CommandParseError::TooLong(column) => write!(
+                f,
+                "repeat at column {} runs more than {} steps",
+                column, MAX_STEPS
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Forward(i64),
+    Back(i64),
+    Down(i64),
+    Up(i64),
+    ResetAim,
+    Repeat(usize, Vec<Command>),
+}
+
+// How many steps a whole script can take.  Every pass round a repeat counts
+// as at least one, so `repeat 1000000000000 {}` is too long as well.
+pub const MAX_STEPS: usize = 100_000_000;
+
+#[derive(Debug, PartialEq)]
+pub struct Overflow;
+
+impl std::error::Error for Overflow {}
+
+impl std::fmt::Display for Overflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "the submarine went further than an i64 can count")
+    }
+}
+
+impl std::fmt::Display for Command {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Command::Forward(n) => write!(f, "forward {}", n),
+            Command::Back(n) => write!(f, "back {}", n),
+            Command::Down(n) => write!(f, "down {}", n),
+            Command::Up(n) => write!(f, "up {}", n),
+            Command::ResetAim => write!(f, "reset-aim"),
+            Command::Repeat(times, block) => {
+                write!(f, "repeat {} {{", times)?;
+                for command in block {
+                    write!(f, " {}", command)?;
+                }
+                write!(f, " }}")
+            }
+        }
+    }
+}
+
+// A word of the script, and where it was.
+struct Token<'a> {
+    text: &'a str,
+    line: usize,
+    column: usize,
+}
+
+// Anything after a # is a comment, braces are words by themselves, and
+// otherwise words are split by whitespace.  Line breaks don't matter, so a
+// repeat can be on one line or spread over several.
+fn tokenise(lines: &[String]) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+
+    for (n, line) in lines.iter().enumerate() {
+        let code = line.split('#').next().unwrap_or("");
+        for word in code.split_whitespace() {
+            let mut rest = word;
+            while !rest.is_empty() {
+                let end = match rest.find(['{', '}']) {
+                    Some(0) => 1,
+                    Some(brace) => brace,
+                    None => rest.len(),
+                };
+                tokens.push(Token {
+                    text: &rest[..end],
+                    line: n + 1,
+                    column: common::parse::column(line, rest),
+                });
+                rest = &rest[end..];
+            }
+        }
+    }
+
+    tokens
+}
+
+type Tokens<'a, 'b> = std::iter::Peekable<std::slice::Iter<'b, Token<'a>>>;
+
+fn value<T: std::str::FromStr>(
+    tokens: &mut Tokens,
+    verb: &Token,
+) -> Result<T, Located<CommandParseError>> {
+    let token = tokens.next().ok_or(Located::new(
+        verb.line,
+        CommandParseError::Missing("a value", verb.text.to_string()),
+    ))?;
+
+    token.text.parse::<T>().map_err(|_| {
+        Located::new(
+            token.line,
+            CommandParseError::Value(token.column, token.text.to_string()),
+        )
+    })
+}
+
+// Commands up to the end of the script, or the } closing the `repeat` we're
+// in, and how many steps they take.
+fn block(
+    tokens: &mut Tokens,
+    repeat: Option<&Token>,
+) -> Result<(Vec<Command>, usize), Located<CommandParseError>> {
+    let mut commands = vec![];
+    let mut steps = 0;
+    let too_long =
+        |token: &Token| Located::new(token.line, CommandParseError::TooLong(token.column));
+
+    while let Some(token) = tokens.next() {
+        let command = match token.text {
+            "forward" => Command::Forward(value(tokens, token)?),
+            "back" => Command::Back(value(tokens, token)?),
+            "down" => Command::Down(value(tokens, token)?),
+            "up" => Command::Up(value(tokens, token)?),
+            "reset-aim" => Command::ResetAim,
+            "repeat" => {
+                let times = value(tokens, token)?;
+                if tokens.next_if(|t| t.text == "{").is_none() {
+                    let missing = CommandParseError::Missing("a {", token.text.to_string());
+                    return Err(Located::new(token.line, missing));
+                }
+                let (inner, inner_steps) = block(tokens, Some(token))?;
+                steps = usize::checked_mul(times, inner_steps.max(1))
+                    .and_then(|repeated| repeated.checked_add(steps))
+                    .filter(|&steps| steps <= MAX_STEPS)
+                    .ok_or_else(|| too_long(token))?;
+                commands.push(Command::Repeat(times, inner));
+                continue;
+            }
+            "}" => match repeat {
+                Some(_) => return Ok((commands, steps)),
+                None => {
+                    let unopened = CommandParseError::Unopened(token.column);
+                    return Err(Located::new(token.line, unopened));
+                }
+            },
+            verb => {
+                let unknown = CommandParseError::Verb(verb.to_string());
+                return Err(Located::new(token.line, unknown));
+            }
+        };
+        // A script can only get past MAX_STEPS a step at a time by being
+        // longer than that, which takes no longer to run than to read.
+        steps += 1;
+        commands.push(command);
+    }
+
+    match repeat {
+        Some(repeat) => Err(Located::new(repeat.line, CommandParseError::Unclosed)),
+        None => Ok((commands, steps)),
+    }
+}
+
+// A script of commands, one per line in the puzzle but they can go anywhere.
+pub fn parse_script(lines: &[String]) -> Result<Vec<Command>, Located<CommandParseError>> {
+    let tokens = tokenise(lines);
+
+    block(&mut tokens.iter().peekable(), None).map(|(commands, _)| commands)
+}
+
+// Every command a script runs, in order, with repeats unrolled as it goes.
+pub struct Moves<'a> {
+    // what's left of each block we're in, and how many more times to go
+    // round it
+    stack: Vec<(&'a [Command], &'a [Command], usize)>,
+}
+
+impl<'a> Iterator for Moves<'a> {
+    type Item = &'a Command;
+
+    fn next(&mut self) -> Option<&'a Command> {
+        loop {
+            let (block, rest, times) = self.stack.last_mut()?;
+            match rest.split_first() {
+                Some((Command::Repeat(n, inner), after)) => {
+                    *rest = after;
+                    if *n > 0 {
+                        self.stack.push((inner, inner, n - 1));
+                    }
+                }
+                Some((command, after)) => {
+                    *rest = after;
+                    return Some(command);
+                }
+                None if *times > 0 => {
+                    *rest = block;
+                    *times -= 1;
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+}
+
+pub fn moves(commands: &[Command]) -> Moves<'_> {
+    Moves {
+        stack: vec![(commands, commands, 0)],
+    }
+}
+
+// How to read up and down.  Part 1 thought they moved the submarine up and
+// down, part 2 that they turn it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    Depth,
+    Aim,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Submarine {
+    horiz: i64,
+    depth: i64,
+    aim: i64,
+}
+
+impl Submarine {
+    pub fn horiz(&self) -> i64 {
+        self.horiz
+    }
+
+    pub fn depth(&self) -> i64 {
+        self.depth
+    }
+
+    // Always 0 in depth mode.
+    pub fn aim(&self) -> i64 {
+        self.aim
+    }
+
+    // What part 1 and part 2 multiply together for their answers.
+    pub fn product(&self) -> Result<i64, Overflow> {
+        self.horiz.checked_mul(self.depth).ok_or(Overflow)
+    }
+
+    fn move_vertically(&self, mode: Mode, val: i64) -> Result<Submarine, Overflow> {
+        Ok(match mode {
+            Mode::Depth => Submarine {
+                depth: self.depth.checked_add(val).ok_or(Overflow)?,
+                ..*self
+            },
+            Mode::Aim => Submarine {
+                aim: self.aim.checked_add(val).ok_or(Overflow)?,
+                ..*self
+            },
+        })
+    }
+
+    fn move_horizontally(&self, val: i64) -> Result<Submarine, Overflow> {
+        let deeper = self.aim.checked_mul(val).ok_or(Overflow)?;
+
+        Ok(Submarine {
+            horiz: self.horiz.checked_add(val).ok_or(Overflow)?,
+            depth: self.depth.checked_add(deeper).ok_or(Overflow)?,
+            aim: self.aim,
+        })
+    }
+
+    pub fn follow(&self, mode: Mode, com: &Command) -> Result<Submarine, Overflow> {
+        let negate = |val: &i64| val.checked_neg().ok_or(Overflow);
+
+        match com {
+            Command::Up(val) => self.move_vertically(mode, negate(val)?),
+            Command::Down(val) => self.move_vertically(mode, *val),
+            Command::Forward(val) => self.move_horizontally(*val),
+            Command::Back(val) => self.move_horizontally(negate(val)?),
+            Command::ResetAim => Ok(Submarine { aim: 0, ..*self }),
+            Command::Repeat(times, block) => (0..*times).try_fold(*self, |sub, _| {
+                block.iter().try_fold(sub, |sub, com| sub.follow(mode, com))
+            }),
+        }
+    }
+}
+
+fn parse(lines: Vec<String>) -> Result<Vec<Command>, Box<dyn std::error::Error>> {
+    // as before, we're treating parsing separately from solution logic
+    let commands = parse_script(&lines)?;
+
+    if commands.is_empty() {
+        return Err(CommandParseError::Empty.into());
+    }
+    Ok(commands)
+}
+
+pub fn navigate(commands: &[Command], mode: Mode) -> Result<Submarine, Overflow> {
+    commands
+        .iter()
+        .try_fold(Submarine::default(), |pos, com| pos.follow(mode, com))
+}
+
+// Where the submarine was after `step` commands, read both ways.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Waypoint {
+    pub step: usize,
+    pub part1: Submarine,
+    pub part2: Submarine,
+}
+
+impl Waypoint {
+    fn start() -> Waypoint {
+        Waypoint {
+            step: 0,
+            part1: Submarine::default(),
+            part2: Submarine::default(),
+        }
+    }
+
+    fn next(&self, com: &Command) -> Result<Waypoint, Overflow> {
+        Ok(Waypoint {
+            step: self.step + 1,
+            part1: self.part1.follow(Mode::Depth, com)?,
+            part2: self.part2.follow(Mode::Aim, com)?,
+        })
+    }
+}
+
+type Traced<'a> = Result<(Option<&'a Command>, Waypoint), Overflow>;
+
+// Every waypoint on the way, starting from the surface before any commands.
+// A repeat counts a step for each command it runs.  If the submarine goes
+// too far the last item is the error, and there's nothing after it.
+pub fn trace(commands: &[Command]) -> impl Iterator<Item = Traced<'_>> {
+    let start = Ok((None, Waypoint::start()));
+
+    std::iter::once(start).chain(
+        moves(commands).scan(Some(Waypoint::start()), |waypoint, com| {
+            let next = waypoint.take()?.next(com);
+            *waypoint = next.as_ref().ok().copied();
+            Some(next.map(|next| (Some(com), next)))
+        }),
+    )
+}
+
+// Where the submarine was after the first `step` commands, or None if there
+// aren't that many.
+pub fn replay(commands: &[Command], step: usize) -> Result<Option<Waypoint>, Overflow> {
+    let mut reached = Waypoint::start();
+
+    // Going too far before `step` is still going too far.
+    for traced in trace(commands).take(step + 1) {
+        reached = traced?.1;
+    }
+    Ok(Some(reached).filter(|waypoint| waypoint.step == step))
+}
+
+pub const CSV_HEADER: &str = "step,command,horiz1,depth1,horiz2,depth2,aim2";
+
+// The whole trajectory as CSV, a row per waypoint.  Columns ending in 1 are
+// part 1's reading of the commands, 2 part 2's.
+pub fn to_csv(commands: &[Command]) -> Result<String, Overflow> {
+    let mut csv = String::from(CSV_HEADER);
+
+    for traced in trace(commands) {
+        let (command, waypoint) = traced?;
+        let command = command.map(|c| c.to_string()).unwrap_or_default();
+        let (p1, p2) = (waypoint.part1, waypoint.part2);
+        csv.push_str(&format!(
+            "\n{},{},{},{},{},{},{}",
+            waypoint.step, command, p1.horiz, p1.depth, p2.horiz, p2.depth, p2.aim
+        ));
+    }
+    csv.push('\n');
+
+    Ok(csv)
+}
+
+const EXAMPLE: &str = "forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2";
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: usize = 2;
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("150"),
+        part2: Some("900"),
+    }];
+
+    type Input = Vec<Command>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input, Box<dyn std::error::Error>> {
+        parse(lines)
+    }
+
+    fn part1(commands: &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+        let final_position = navigate(commands, Mode::Depth)?;
+
+        common::note("horiz", final_position.horiz);
+        common::note("depth", final_position.depth);
+
+        Ok(final_position.product()?)
+    }
+
+    fn part2(commands: &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+        let final_position = navigate(commands, Mode::Aim)?;
+
+        common::note("horiz", final_position.horiz);
+        common::note("depth", final_position.depth);
+        common::note("aim", final_position.aim);
+
+        Ok(final_position.product()?)
+    }
+
+    // `size` commands.
+    fn generate(rng: &mut Rng, size: Option<usize>) -> Vec<String> {
+        (0..size.unwrap_or(1000))
+            .map(|_| {
+                let direction = rng.choose(&["forward", "forward", "down", "up"]);
+                format!("{} {}", direction, rng.between(1, 9))
+            })
+            .collect()
+    }
+}
+
+pub fn run(source: Option<Source>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    common::run::<Day2>(source)
+}
+
+common::solution_tests!(Day2);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn example() -> Vec<Command> {
+        parse(EXAMPLE.lines().map(String::from).collect()).unwrap()
+    }
+
+    #[test]
+    fn trace_test() {
+        let commands = example();
+        let waypoints: Vec<Waypoint> = trace(&commands).map(|t| t.unwrap().1).collect();
+
+        assert!(waypoints.len() == 7 && waypoints[0] == Waypoint::start());
+        let last = waypoints[6];
+        assert!(last.part1.horiz * last.part1.depth == 150 && last.part1.aim == 0);
+        assert!(Ok(last.part1) == navigate(&commands, Mode::Depth));
+        assert!(Ok(last.part2) == navigate(&commands, Mode::Aim));
+        assert!(waypoints.iter().enumerate().all(|(n, w)| w.step == n));
+    }
+
+    #[test]
+    fn replay_test() {
+        let commands = example();
+
+        // forward 5, down 5, forward 8
+        let waypoint = replay(&commands, 3).unwrap().unwrap();
+        assert!((waypoint.part1.horiz, waypoint.part1.depth) == (13, 5));
+        assert!(
+            (
+                waypoint.part2.horiz,
+                waypoint.part2.depth,
+                waypoint.part2.aim
+            ) == (13, 40, 5)
+        );
+        assert!(replay(&commands, 0) == Ok(Some(Waypoint::start())));
+        let last = trace(&commands).last().map(|t| t.unwrap().1);
+        assert!(replay(&commands, 6) == Ok(last));
+        assert!(replay(&commands, 7) == Ok(None));
+    }
+
+    #[test]
+    fn csv_test() {
+        let csv = to_csv(&example()).unwrap();
+        let rows: Vec<&str> = csv.lines().collect();
+
+        assert!(rows.len() == 8 && rows[0] == CSV_HEADER);
+        assert!(rows[1] == "0,,0,0,0,0,0");
+        assert!(rows[3] == "2,down 5,5,5,5,0,5");
+        assert!(rows[7] == "6,forward 2,15,10,15,60,10");
+    }
+
+    fn script(text: &str) -> Result<Vec<Command>, Located<CommandParseError>> {
+        parse_script(&text.lines().map(String::from).collect::<Vec<String>>())
+    }
+
+    #[test]
+    fn script_test() {
+        let commands = script(
+            "# go a long way
+            forward 1000  # more than a u8
+            repeat 2 {
+                down 3
+                repeat 2 { forward 1 } back -2
+            }
+            reset-aim
+            up 10",
+        )
+        .unwrap();
+        assert!(commands.len() == 4 && commands[0] == Command::Forward(1000));
+        assert!(commands[1].to_string() == "repeat 2 { down 3 repeat 2 { forward 1 } back -2 }");
+
+        let unrolled: Vec<String> = moves(&commands).map(|c| c.to_string()).collect();
+        assert!(unrolled.len() == 11);
+        assert!(unrolled[1..5] == ["down 3", "forward 1", "forward 1", "back -2"]);
+
+        let sub = navigate(&commands, Mode::Depth).unwrap();
+        assert!((sub.horiz(), sub.depth(), sub.aim()) == (1008, -4, 0));
+        // 3 * 4 then 6 * 4 deeper, then up 10 from level
+        let sub = navigate(&commands, Mode::Aim).unwrap();
+        assert!((sub.horiz(), sub.depth(), sub.aim()) == (1008, 36, -10));
+        assert!(replay(&commands, 11).unwrap().unwrap().part2 == sub);
+
+        // An empty repeat runs nothing, and so does a repeat 0.
+        assert!(moves(&script("repeat 3 {} repeat 0 { up 1 }").unwrap()).count() == 0);
+    }
+
+    #[test]
+    fn script_error_test() {
+        let error = |text| script(text).unwrap_err();
+
+        assert!(
+            error("forward 1\nsideways 2")
+                == Located::new(2, CommandParseError::Verb("sideways".to_string()))
+        );
+        assert!(
+            error("up\n")
+                == Located::new(1, CommandParseError::Missing("a value", "up".to_string()))
+        );
+        assert!(
+            error("down  x2") == Located::new(1, CommandParseError::Value(7, "x2".to_string()))
+        );
+        assert!(
+            error("repeat 2 forward 1")
+                == Located::new(1, CommandParseError::Missing("a {", "repeat".to_string()))
+        );
+        assert!(error("repeat 2 {\n  up 1") == Located::new(1, CommandParseError::Unclosed));
+        assert!(error("up 1 }") == Located::new(1, CommandParseError::Unopened(6)));
+        assert!(error("repeat -1 {}").error == CommandParseError::Value(8, "-1".to_string()));
+
+        // Each of these is fine on its own, but not nested or one after the
+        // other.
+        let forever = "repeat 18446744073709551615 { forward 1 }";
+        assert!(error(forever) == Located::new(1, CommandParseError::TooLong(1)));
+        assert!(script("repeat 10000 { down 1 }").is_ok());
+        let nested = "repeat 10000 {\n  repeat 10000 { down 1 } up 1\n}";
+        assert!(error(nested) == Located::new(1, CommandParseError::TooLong(1)));
+        let twice = "repeat 100000000 {}\nrepeat 1 {}";
+        assert!(error(twice) == Located::new(2, CommandParseError::TooLong(1)));
+    }
+
+    #[test]
+    fn overflow_test() {
+        let too_far = script("forward 9223372036854775807\nforward 1").unwrap();
+        assert!(navigate(&too_far, Mode::Depth) == Err(Overflow));
+
+        let commands = script("down 3037000500\nforward 3037000500").unwrap();
+        let sub = navigate(&commands, Mode::Depth).unwrap();
+        assert!(sub.product() == Err(Overflow));
+        assert!(Day2::part1(&commands).is_err());
+        assert!(navigate(&commands, Mode::Aim) == Err(Overflow));
+
+        // The trace stops at the step that went too far.
+        let traced: Vec<Traced> = trace(&too_far).collect();
+        assert!(traced.len() == 3 && traced[2] == Err(Overflow));
+        assert!(replay(&too_far, 1).unwrap().is_some());
+        assert!(replay(&too_far, 2) == Err(Overflow));
+        assert!(replay(&too_far, 3) == Err(Overflow));
+        assert!(to_csv(&too_far) == Err(Overflow));
+        assert!(navigate(&script("up -9223372036854775808").unwrap(), Mode::Aim) == Err(Overflow));
+    }
+}
@@ -0,0 +1,296 @@
+use common::image::Colour;
+use common::{Example, Grid, Located, Rng, Simulation, Solution, Source, Visualise};
+
+#[derive(Debug, PartialEq)]
+pub enum ImageError {
+    // column, and what was there
+    Pixel(usize, char),
+    // how many pixels the algorithm had
+    Algorithm(usize),
+    Blank,
+}
+
+impl std::error::Error for ImageError {}
+
+impl std::fmt::Display for ImageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ImageError::Pixel(column, c) => {
+                write!(f, "\"{}\" at column {} isn't a # or .", c, column)
+            }
+            ImageError::Algorithm(n) => {
+                write!(f, "enhancement algorithm is {} pixels, expected 512", n)
+            }
+            ImageError::Blank => write!(f, "expected a blank line after the algorithm"),
+        }
+    }
+}
+
+fn pixel(c: char) -> Option<bool> {
+    match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+fn print_image(image: &Grid<bool>) {
+    println!("{}", image.render(|&p| if p { '#' } else { '.' }));
+}
+
+// Read the 3x3 square around a pixel as a binary number.  Anything off the
+// edge is the same as the infinite image beyond it, which matches the corner.
+fn get_square_value(image: &Grid<bool>, x: usize, y: usize) -> usize {
+    let outside = image[(0, 0)];
+
+    (-1..=1)
+        .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+        .map(|(dx, dy)| {
+            let x = x.checked_add_signed(dx);
+            let y = y.checked_add_signed(dy);
+            match (x, y) {
+                (Some(x), Some(y)) => *image.get(x, y).unwrap_or(&outside),
+                _ => outside,
+            }
+        })
+        .fold(0, |acc, p| acc << 1 | p as usize)
+}
+
+// The enhancement algorithm, and the input image.
+pub type Enhancement = (Vec<bool>, Grid<bool>);
+
+fn parse_algorithm(line: &str) -> Result<Vec<bool>, ImageError> {
+    let algo = line
+        .chars()
+        .enumerate()
+        .map(|(n, c)| pixel(c).ok_or(ImageError::Pixel(n + 1, c)))
+        .collect::<Result<Vec<bool>, ImageError>>()?;
+
+    // Any 9 pixels can be looked up in it.
+    if algo.len() != 512 {
+        return Err(ImageError::Algorithm(algo.len()));
+    }
+    Ok(algo)
+}
+
+fn parse(lines: Vec<String>) -> Result<Enhancement, Box<dyn std::error::Error>> {
+    let algo = parse_algorithm(lines.first().map_or("", |l| l.as_str()))
+        .map_err(|e| Located::new(1, e))?;
+    if lines.get(1).is_some_and(|l| !l.is_empty()) {
+        return Err(Located::new(2, ImageError::Blank).into());
+    }
+    // The image starts on line 3.
+    let page = Grid::parse(lines.get(2..).unwrap_or(&[]), pixel).map_err(|e| e.offset(2))?;
+
+    Ok((algo, page))
+}
+
+fn grow_image(image: &Grid<bool>, inf: bool) -> Grid<bool> {
+    let mut grown = Grid::new(image.width() + 4, image.height() + 4, inf);
+
+    for (x, y) in image.positions() {
+        grown[(x + 2, y + 2)] = image[(x, y)];
+    }
+
+    grown
+}
+
+fn update_image(old: &Grid<bool>, algo: &[bool]) -> Grid<bool> {
+    let grown = grow_image(old, old[(0, 0)]);
+    let mut updated = grown.clone();
+
+    for (x, y) in grown.positions() {
+        updated[(x, y)] = algo[get_square_value(&grown, x, y)];
+    }
+
+    updated
+}
+
+// Enhance the image a number of times, and count the lit pixels.
+pub fn enhance(algo: &[bool], image: &Grid<bool>, times: usize) -> usize {
+    let grown = grow_image(image, false);
+    let mut enhanced = grown;
+
+    for _ in 0..times {
+        enhanced = update_image(&enhanced, algo);
+    }
+
+    enhanced.iter().filter(|&p| *p).count()
+}
+
+// The image being enhanced as many times as part 2 wants.
+pub struct Enhancing {
+    algo: Vec<bool>,
+    image: Grid<bool>,
+    times: usize,
+}
+
+impl Simulation for Enhancing {
+    fn step(&mut self) -> bool {
+        if self.times == 50 {
+            return false;
+        }
+        self.image = update_image(&self.image, &self.algo);
+        self.times += 1;
+
+        true
+    }
+
+    fn render(&self) -> String {
+        let lit = self.image.iter().filter(|&p| *p).count();
+
+        format!(
+            "enhanced {} times, {} pixels lit\n{}",
+            self.times,
+            lit,
+            self.image.render(|&p| if p { '#' } else { '.' })
+        )
+    }
+
+    fn picture(&self) -> Option<Grid<Colour>> {
+        Some(
+            self.image
+                .map(|&p| if p { Colour::WHITE } else { Colour::BLACK }),
+        )
+    }
+}
+
+const EXAMPLE: &str = "..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..###..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###.######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#..#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#......#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#.....####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.......##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#
+
+#..#.
+#....
+##..#
+..#..
+..###";
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: usize = 20;
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("35"),
+        part2: Some("3351"),
+    }];
+
+    type Input = Enhancement;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input, Box<dyn std::error::Error>> {
+        parse(lines)
+    }
+
+    fn part1((algo, image): &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+        Ok(enhance(algo, image, 2))
+    }
+
+    fn part2((algo, image): &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+        Ok(enhance(algo, image, 50))
+    }
+
+    // A random algorithm and a `size` x `size` image.  If the algorithm lights
+    // up the infinite dark space, it has to turn it off again the next time
+    // round, or the answer would be infinite too.
+    fn generate(rng: &mut Rng, size: Option<usize>) -> Vec<String> {
+        let pixel = |rng: &mut Rng| if rng.chance(1, 2) { '#' } else { '.' };
+        let mut algorithm: Vec<char> = (0..512).map(|_| pixel(rng)).collect();
+        if algorithm[0] == '#' {
+            algorithm[511] = '.';
+        }
+
+        let size = size.unwrap_or(100).max(1);
+        let mut lines = vec![algorithm.into_iter().collect(), String::new()];
+        for _ in 0..size {
+            lines.push((0..size).map(|_| pixel(rng)).collect());
+        }
+
+        lines
+    }
+}
+
+impl Visualise for Day20 {
+    fn simulation((algo, image): &Self::Input) -> Box<dyn Simulation> {
+        Box::new(Enhancing {
+            algo: algo.clone(),
+            image: grow_image(image, false),
+            times: 0,
+        })
+    }
+}
+
+pub fn run(source: Option<Source>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    common::run::<Day20>(source)
+}
+
+common::solution_tests!(Day20);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn index_test() {
+        let (algo, image) = parse(
+            EXAMPLE
+                .lines()
+                .map(|l| l.to_string())
+                .collect::<Vec<String>>(),
+        )
+        .unwrap();
+
+        let index = get_square_value(&image, 2, 2);
+
+        assert!(index == 34);
+        assert!(algo[34]);
+    }
+
+    #[test]
+    fn grow_test() {
+        let (_algo, image) = parse(
+            EXAMPLE
+                .lines()
+                .map(|l| l.to_string())
+                .collect::<Vec<String>>(),
+        )
+        .unwrap();
+
+        let grown_image = grow_image(&image, false);
+
+        assert!(grown_image.height() == 9);
+        assert!(grown_image.width() == 9);
+        assert!(get_square_value(&grown_image, 4, 4) == 34);
+    }
+
+    #[test]
+    fn update_test() {
+        let (algo, image) = parse(
+            EXAMPLE
+                .lines()
+                .map(|l| l.to_string())
+                .collect::<Vec<String>>(),
+        )
+        .unwrap();
+
+        let grown = grow_image(&image, false);
+        print_image(&grown);
+        let updated = update_image(&grown, &algo);
+        print_image(&updated);
+        let updated_twice = update_image(&updated, &algo);
+        print_image(&updated_twice);
+        assert!(updated_twice.iter().filter(|&p| *p).count() == 35);
+    }
+
+    #[test]
+    fn simulation_test() {
+        let input = parse(EXAMPLE.lines().map(String::from).collect()).unwrap();
+        let mut simulation = Day20::simulation(&input);
+
+        assert!(simulation.step() && simulation.step());
+        assert!(simulation
+            .render()
+            .starts_with("enhanced 2 times, 35 pixels lit\n"));
+    }
+}
@@ -0,0 +1,261 @@
+use common::{Example, Located, Rng, Solution, Source};
+use std::{collections::HashMap, hash::Hash, iter::Sum};
+
+#[derive(Debug, PartialEq)]
+pub enum StartParseError {
+    Player(usize),
+    // column, and what was there
+    Position(usize, String),
+}
+
+impl std::error::Error for StartParseError {}
+
+impl std::fmt::Display for StartParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            StartParseError::Player(n) => {
+                write!(f, "expected \"Player {} starting position: \"", n)
+            }
+            StartParseError::Position(column, pos) => write!(
+                f,
+                "\"{}\" at column {} isn't a position between 1 and 10",
+                pos, column
+            ),
+        }
+    }
+}
+
+// "Player 1 starting position: 4"
+fn parse_start(line: Option<&String>, player: usize) -> Result<usize, StartParseError> {
+    let prefix = format!("Player {} starting position: ", player);
+    let line = line.map_or("", |l| l.as_str());
+    let pos = line
+        .trim()
+        .strip_prefix(&prefix)
+        .ok_or(StartParseError::Player(player))?;
+
+    match pos.parse::<usize>() {
+        Ok(n) if (1..=10).contains(&n) => Ok(n),
+        _ => Err(StartParseError::Position(
+            common::parse::column(line, pos),
+            pos.to_string(),
+        )),
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+struct GameResult {
+    p1_wins: usize,
+    p2_wins: usize,
+}
+
+impl<'a> Sum<&'a Self> for GameResult {
+    fn sum<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = &'a Self>,
+    {
+        iter.fold(
+            Self {
+                p1_wins: 0,
+                p2_wins: 0,
+            },
+            |x, y| Self {
+                p1_wins: x.p1_wins + y.p1_wins,
+                p2_wins: x.p2_wins + y.p2_wins,
+            },
+        )
+    }
+}
+
+// I definitely don't know how many of these I actually need.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+struct GameState {
+    p1_score: usize,
+    p2_score: usize,
+    p1_pos: usize,
+    p2_pos: usize,
+    p1_turn: bool,
+    nth_roll: usize,
+    roll_sum: usize,
+}
+
+impl GameState {
+    fn update(&self, roll: usize) -> Self {
+        let mut new = *self;
+        new.nth_roll += 1;
+        new.roll_sum += roll;
+
+        if new.nth_roll == 3 {
+            if self.p1_turn {
+                new.p1_pos = (new.p1_pos + new.roll_sum) % 10;
+                if new.p1_pos == 0 {
+                    new.p1_pos = 10;
+                }
+                new.p1_score += new.p1_pos;
+            } else {
+                new.p2_pos = (new.p2_pos + new.roll_sum) % 10;
+                if new.p2_pos == 0 {
+                    new.p2_pos = 10;
+                }
+                new.p2_score += new.p2_pos;
+            }
+            new.nth_roll = 0;
+            new.p1_turn = !self.p1_turn;
+            new.roll_sum = 0;
+        }
+
+        new
+    }
+}
+
+pub fn deterministic_game(p1_start: usize, p2_start: usize) -> usize {
+    let mut roll_count = 0;
+    let mut state = GameState {
+        p1_score: 0,
+        p2_score: 0,
+        p1_pos: p1_start,
+        p2_pos: p2_start,
+        p1_turn: true,
+        nth_roll: 0,
+        roll_sum: 0,
+    };
+
+    while state.p1_score < 1000 && state.p2_score < 1000 {
+        state = state.update((roll_count % 100) + 1);
+        roll_count += 1;
+    }
+
+    state.p1_score.min(state.p2_score) * roll_count
+}
+
+fn universe(state: GameState, database: &mut HashMap<GameState, GameResult>) {
+    if database.contains_key(&state) {
+        // We've already been here.
+    } else if state.p1_score >= 21 {
+        database.insert(
+            state,
+            GameResult {
+                p1_wins: 1,
+                p2_wins: 0,
+            },
+        );
+    } else if state.p2_score >= 21 {
+        database.insert(
+            state,
+            GameResult {
+                p1_wins: 0,
+                p2_wins: 1,
+            },
+        );
+    } else {
+        let results = [1, 2, 3]
+            .iter()
+            .map(|n| {
+                let new_state = state.update(*n);
+                if !database.contains_key(&new_state) {
+                    universe(new_state, database);
+                }
+                database[&new_state]
+            })
+            .collect::<Vec<GameResult>>()
+            .iter()
+            .sum(); // Not entirely sure why it won't let me sum without collecting
+        database.insert(state, results);
+    }
+}
+
+pub fn dirac_game(p1_start: usize, p2_start: usize) -> usize {
+    let base_state = GameState {
+        p1_score: 0,
+        p2_score: 0,
+        p1_pos: p1_start,
+        p2_pos: p2_start,
+        p1_turn: true,
+        nth_roll: 0,
+        roll_sum: 0,
+    };
+
+    let mut database: HashMap<GameState, GameResult> = HashMap::new();
+
+    universe(base_state, &mut database);
+
+    let result = database[&base_state];
+    result.p1_wins.max(result.p2_wins)
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: usize = 21;
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: "Player 1 starting position: 4\nPlayer 2 starting position: 8",
+        part1: Some("739785"),
+        part2: Some("444356092776315"),
+    }];
+
+    // Each player's starting position.
+    type Input = (usize, usize);
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input, Box<dyn std::error::Error>> {
+        Ok((
+            parse_start(lines.first(), 1).map_err(|e| Located::new(1, e))?,
+            parse_start(lines.get(1), 2).map_err(|e| Located::new(2, e))?,
+        ))
+    }
+
+    fn part1(
+        &(p1_start, p2_start): &Self::Input,
+    ) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+        Ok(deterministic_game(p1_start, p2_start))
+    }
+
+    fn part2(
+        &(p1_start, p2_start): &Self::Input,
+    ) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+        Ok(dirac_game(p1_start, p2_start))
+    }
+
+    // Two random starting positions.  There's nothing for `size` to change.
+    fn generate(rng: &mut Rng, _size: Option<usize>) -> Vec<String> {
+        (1..=2)
+            .map(|player| {
+                format!(
+                    "Player {} starting position: {}",
+                    player,
+                    rng.between(1, 10)
+                )
+            })
+            .collect()
+    }
+}
+
+pub fn run(source: Option<Source>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    common::run::<Day21>(source)
+}
+
+common::solution_tests!(Day21);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        let lines = vec![
+            "Player 1 starting position: 4".to_string(),
+            "Player 2 starting position: 8".to_string(),
+        ];
+
+        assert!(Day21::parse(lines).unwrap() == (4, 8));
+        assert!(deterministic_game(4, 8) == 739785);
+        assert!(parse_start(Some(&"Player 2 starting position: 8".to_string()), 1).is_err());
+        assert!(
+            parse_start(Some(&"Player 1 starting position: 11".to_string()), 1)
+                == Err(StartParseError::Position(29, "11".to_string()))
+        );
+        assert!(parse_start(None, 2).is_err());
+    }
+}
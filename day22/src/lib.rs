@@ -0,0 +1,334 @@
+use common::{Example, Located, Rng, Solution, Source};
+use std::str::FromStr;
+
+#[derive(Debug, Copy, Clone)]
+struct Range {
+    min: isize,
+    max: isize,
+}
+
+impl Range {
+    fn new(min: isize, max: isize) -> Range {
+        assert!(min <= max);
+        Range { min, max }
+    }
+
+    // Returns the overlapping range, if any.
+    fn intersect(&self, other: &Range) -> Option<Range> {
+        if other.min <= self.max && self.min <= other.max {
+            let intersection_min = self.min.max(other.min);
+            let intersection_max = self.max.min(other.max);
+            Some(Range::new(intersection_min, intersection_max))
+        } else {
+            None
+        }
+    }
+
+    fn count(&self) -> isize {
+        (self.max - self.min) + 1
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Cuboid {
+    on: bool,
+    x: Range,
+    y: Range,
+    z: Range,
+    score: isize,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CuboidParseError {
+    State,
+    Axis(char),
+    // column, and what was there
+    Bound(usize, String),
+}
+
+impl std::error::Error for CuboidParseError {}
+
+impl std::fmt::Display for CuboidParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CuboidParseError::State => write!(f, "expected \"on\" or \"off\""),
+            CuboidParseError::Axis(axis) => write!(f, "expected a range like {}=1..2", axis),
+            CuboidParseError::Bound(column, bound) => {
+                write!(f, "\"{}\" at column {} isn't a number", bound, column)
+            }
+        }
+    }
+}
+
+// "x=10..12", somewhere in `line`
+fn parse_range(line: &str, buf: Option<&str>, axis: char) -> Result<Range, CuboidParseError> {
+    let (start, end) = buf
+        .and_then(|b| b.strip_prefix(axis))
+        .and_then(|b| b.strip_prefix('='))
+        .and_then(|b| b.split_once(".."))
+        .ok_or(CuboidParseError::Axis(axis))?;
+    let parse_bound = |b: &str| {
+        b.parse::<isize>()
+            .map_err(|_| CuboidParseError::Bound(common::parse::column(line, b), b.to_string()))
+    };
+    let (start, end) = (parse_bound(start)?, parse_bound(end)?);
+
+    Ok(Range::new(start.min(end), start.max(end)))
+}
+
+// "on x=10..12,y=10..12,z=10..12"
+impl FromStr for Cuboid {
+    type Err = CuboidParseError;
+
+    fn from_str(buf: &str) -> Result<Self, Self::Err> {
+        let (on, coords) = buf.trim().split_once(' ').ok_or(Self::Err::State)?;
+        let on = match on {
+            "on" => true,
+            "off" => false,
+            _ => return Err(Self::Err::State),
+        };
+
+        let mut coords = coords.split(',');
+        let x = parse_range(buf, coords.next(), 'x')?;
+        let y = parse_range(buf, coords.next(), 'y')?;
+        let z = parse_range(buf, coords.next(), 'z')?;
+        if coords.next().is_some() {
+            return Err(Self::Err::Axis('z'));
+        }
+
+        Ok(Cuboid::new(on, x, y, z))
+    }
+}
+
+impl Cuboid {
+    fn new(on: bool, x: Range, y: Range, z: Range) -> Cuboid {
+        Cuboid {
+            on,
+            x,
+            y,
+            z,
+            score: x.count() * y.count() * z.count(),
+        }
+    }
+
+    // Whether this step turns its cubes on or off.
+    pub fn is_on(&self) -> bool {
+        self.on
+    }
+
+    // How many cubes are inside, whether they're on or off.
+    pub fn volume(&self) -> isize {
+        self.score
+    }
+
+    // If the other cuboid overlaps with us, return a new cuboid containing the
+    // overlapping region
+    pub fn overlap(&self, other: &Self) -> Option<Cuboid> {
+        if let Some(x) = self.x.intersect(&other.x) {
+            if let Some(y) = self.y.intersect(&other.y) {
+                if let Some(z) = self.z.intersect(&other.z) {
+                    return Some(Cuboid::new(true, x, y, z));
+                }
+            }
+        }
+        None
+    }
+}
+
+fn parse(lines: Vec<String>) -> Result<Vec<Cuboid>, Box<dyn std::error::Error>> {
+    Ok(lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(n, l)| l.parse::<Cuboid>().map_err(|e| Located::new(n + 1, e)))
+        .collect::<Result<Vec<Cuboid>, Located<CuboidParseError>>>()?)
+}
+
+// Follow the reboot steps in order and count how many cubes end up on,
+// ignoring any step that falls entirely outside the limit.
+pub fn count_cubes(cuboids: &[Cuboid], limit: Option<Cuboid>) -> isize {
+    let mut lights: Vec<Cuboid> = vec![];
+
+    for cuboid in cuboids.iter() {
+        if let Some(limit) = limit {
+            if cuboid.overlap(&limit).is_none() {
+                continue;
+            }
+        }
+        let visited = lights.clone();
+        if cuboid.on {
+            lights.push(*cuboid);
+        }
+        for prev in visited {
+            if let Some(mut new) = prev.overlap(cuboid) {
+                new.on = !prev.on;
+                lights.push(new);
+            }
+        }
+    }
+    let on = lights
+        .iter()
+        .filter(|c| c.on)
+        .map(|c| c.score)
+        .sum::<isize>();
+    let off = lights
+        .iter()
+        .filter(|c| !c.on)
+        .map(|c| c.score)
+        .sum::<isize>();
+
+    on - off
+}
+
+// Part 1 only cares about the cubes in the initialisation area.
+pub fn initialisation_area() -> Cuboid {
+    let range = Range::new(-50, 50);
+
+    Cuboid::new(true, range, range, range)
+}
+
+const EXAMPLE1: &str = "on x=10..12,y=10..12,z=10..12
+on x=11..13,y=11..13,z=11..13
+off x=9..11,y=9..11,z=9..11
+on x=10..10,y=10..10,z=10..10";
+
+const EXAMPLE2: &str = "on x=-20..26,y=-36..17,z=-47..7
+on x=-20..33,y=-21..23,z=-26..28
+on x=-22..28,y=-29..23,z=-38..16
+on x=-46..7,y=-6..46,z=-50..-1
+on x=-49..1,y=-3..46,z=-24..28
+on x=2..47,y=-22..22,z=-23..27
+on x=-27..23,y=-28..26,z=-21..29
+on x=-39..5,y=-6..47,z=-3..44
+on x=-30..21,y=-8..43,z=-13..34
+on x=-22..26,y=-27..20,z=-29..19
+off x=-48..-32,y=26..41,z=-47..-37
+on x=-12..35,y=6..50,z=-50..-2
+off x=-48..-32,y=-32..-16,z=-15..-5
+on x=-18..26,y=-33..15,z=-7..46
+off x=-40..-22,y=-38..-28,z=23..41
+on x=-16..35,y=-41..10,z=-47..6
+off x=-32..-23,y=11..30,z=-14..3
+on x=-49..-5,y=-3..45,z=-29..18
+off x=18..30,y=-20..-8,z=-3..13
+on x=-41..9,y=-7..43,z=-33..15
+on x=-54112..-39298,y=-85059..-49293,z=-27449..7877
+on x=967..23432,y=45373..81175,z=27513..53682";
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: usize = 22;
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: EXAMPLE1,
+            part1: Some("39"),
+            part2: None,
+        },
+        Example {
+            input: EXAMPLE2,
+            part1: Some("590784"),
+            part2: None,
+        },
+    ];
+
+    type Input = Vec<Cuboid>;
+    type Part1 = isize;
+    type Part2 = isize;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input, Box<dyn std::error::Error>> {
+        parse(lines)
+    }
+
+    fn part1(cuboids: &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+        Ok(count_cubes(cuboids, Some(initialisation_area())))
+    }
+
+    fn part2(cuboids: &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+        Ok(count_cubes(cuboids, None))
+    }
+
+    // `size` steps, the first twentieth of them inside the initialisation
+    // area and the rest enormous, like the puzzle's.
+    fn generate(rng: &mut Rng, size: Option<usize>) -> Vec<String> {
+        let steps = size.unwrap_or(420);
+
+        (0..steps)
+            .map(|n| {
+                let (reach, most) = if n < steps.div_ceil(20) {
+                    (50, 50)
+                } else {
+                    (100_000, 50_000)
+                };
+                let mut range = || {
+                    let start = rng.between(-reach, reach - most);
+                    (start, start + rng.between(0, most))
+                };
+                let (x, y, z) = (range(), range(), range());
+                let on = if n == 0 || rng.chance(2, 3) {
+                    "on"
+                } else {
+                    "off"
+                };
+
+                format!(
+                    "{} x={}..{},y={}..{},z={}..{}",
+                    on, x.0, x.1, y.0, y.1, z.0, z.1
+                )
+            })
+            .collect()
+    }
+}
+
+pub fn run(source: Option<Source>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    common::run::<Day22>(source)
+}
+
+common::solution_tests!(Day22);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn example1_overlap() -> Result<(), Box<dyn std::error::Error>> {
+        let cuboids = parse(EXAMPLE1.lines().map(|l| l.to_string()).collect())?;
+
+        assert!(count_cubes(&cuboids, None) == 39);
+
+        Ok(())
+    }
+
+    #[test]
+    fn example2_overlap() -> Result<(), Box<dyn std::error::Error>> {
+        let cuboids = parse(EXAMPLE2.lines().map(|l| l.to_string()).collect())?;
+
+        let limit = Cuboid {
+            on: true,
+            x: Range::new(-50, 50),
+            y: Range::new(-50, 50),
+            z: Range::new(-50, 50),
+            score: 0,
+        };
+
+        assert!(count_cubes(&cuboids, Some(limit)) == 590784);
+
+        Ok(())
+    }
+
+    #[test]
+    fn from_str_test() {
+        let cuboid = "off x=9..11,y=9..11,z=-11..-9".parse::<Cuboid>().unwrap();
+
+        assert!(!cuboid.is_on());
+        assert!(cuboid.volume() == 27);
+        assert!(cuboid.overlap(&initialisation_area()).unwrap().volume() == 27);
+        assert!("of x=9..11,y=9..11,z=9..11".parse::<Cuboid>().is_err());
+        assert!("on x=9..11,y=9..11".parse::<Cuboid>().is_err());
+        assert!(
+            "on x=9..11,y=9..eleven,z=9..11".parse::<Cuboid>().err()
+                == Some(CuboidParseError::Bound(17, "eleven".to_string()))
+        );
+    }
+}
@@ -0,0 +1,576 @@
+use common::progress::OutOfBudget;
+use common::{Example, Located, Progress, Rng, Solution};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+// The column is counted from the start of whatever was being parsed, so it's
+// only really useful from Insn, which moves it to the right place in the line.
+#[derive(Debug, PartialEq)]
+pub enum InsnParseError {
+    Operation(usize, String),
+    Register(usize, String),
+    Operand(usize, String),
+    Missing,
+}
+
+impl InsnParseError {
+    // Move the column along, for text that starts at `column` in the line.
+    fn at(self, column: usize) -> InsnParseError {
+        match self {
+            InsnParseError::Operation(n, s) => InsnParseError::Operation(n + column - 1, s),
+            InsnParseError::Register(n, s) => InsnParseError::Register(n + column - 1, s),
+            InsnParseError::Operand(n, s) => InsnParseError::Operand(n + column - 1, s),
+            InsnParseError::Missing => InsnParseError::Missing,
+        }
+    }
+}
+
+impl std::error::Error for InsnParseError {}
+
+impl std::fmt::Display for InsnParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            InsnParseError::Operation(column, op) => {
+                write!(f, "\"{}\" at column {} isn't an instruction", op, column)
+            }
+            InsnParseError::Register(column, reg) => {
+                write!(f, "\"{}\" at column {} isn't a register", reg, column)
+            }
+            InsnParseError::Operand(column, src) => write!(
+                f,
+                "\"{}\" at column {} isn't a register or a number",
+                src, column
+            ),
+            InsnParseError::Missing => write!(f, "instruction is missing an operand"),
+        }
+    }
+}
+
+// There's no model number MONAD accepts.
+#[derive(Debug, PartialEq, Eq)]
+pub struct NoModelError;
+
+impl std::error::Error for NoModelError {}
+
+impl std::fmt::Display for NoModelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "no model number is valid")
+    }
+}
+
+// The ways a program can crash the ALU.
+#[derive(Debug, PartialEq, Eq)]
+pub enum AluError {
+    DivideByZero,
+    Modulo(isize, isize),
+    Input,
+}
+
+impl std::error::Error for AluError {}
+
+impl std::fmt::Display for AluError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            AluError::DivideByZero => write!(f, "division by zero"),
+            AluError::Modulo(a, b) => write!(f, "can't take {} mod {}", a, b),
+            AluError::Input => write!(f, "ran out of input"),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Operation {
+    Inp,
+    Mul,
+    Add,
+    Mod,
+    Div,
+    Eql,
+}
+
+impl FromStr for Operation {
+    type Err = InsnParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "inp" => Ok(Operation::Inp),
+            "mul" => Ok(Operation::Mul),
+            "add" => Ok(Operation::Add),
+            "mod" => Ok(Operation::Mod),
+            "div" => Ok(Operation::Div),
+            "eql" => Ok(Operation::Eql),
+            _ => Err(Self::Err::Operation(1, text.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Register {
+    W,
+    X,
+    Y,
+    Z,
+}
+
+impl FromStr for Register {
+    type Err = InsnParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "w" => Ok(Register::W),
+            "x" => Ok(Register::X),
+            "y" => Ok(Register::Y),
+            "z" => Ok(Register::Z),
+            _ => Err(Self::Err::Register(1, text.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Source {
+    Immediate(isize),
+    Register(Register),
+}
+
+impl FromStr for Source {
+    type Err = InsnParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        if let Ok(reg) = text.parse::<Register>() {
+            Ok(Source::Register(reg))
+        } else {
+            text.parse::<isize>()
+                .map(Source::Immediate)
+                .map_err(|_| Self::Err::Operand(1, text.to_string()))
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Insn {
+    op: Operation,
+    dst: Register,
+    src: Option<Source>,
+}
+
+// "add x -1", or just "inp w"
+impl FromStr for Insn {
+    type Err = InsnParseError;
+
+    fn from_str(buf: &str) -> Result<Self, Self::Err> {
+        let mut parts = buf.split_whitespace();
+        let column = |part: &str| common::parse::column(buf, part);
+        let extra = |part: &str| Self::Err::Operand(column(part), part.to_string());
+
+        let op = parts.next().ok_or(Self::Err::Missing)?;
+        let op = op.parse::<Operation>().map_err(|e| e.at(column(op)))?;
+        let dst = parts.next().ok_or(Self::Err::Missing)?;
+        let dst = dst.parse::<Register>().map_err(|e| e.at(column(dst)))?;
+        let src = match (op, parts.next()) {
+            (Operation::Inp, None) => None,
+            (Operation::Inp, Some(part)) => return Err(extra(part)),
+            (_, Some(src)) => Some(src.parse::<Source>().map_err(|e| e.at(column(src)))?),
+            (_, None) => return Err(Self::Err::Missing),
+        };
+
+        match parts.next() {
+            Some(part) => Err(extra(part)),
+            None => Ok(Insn { op, dst, src }),
+        }
+    }
+}
+
+impl Insn {
+    pub fn op(&self) -> Operation {
+        self.op
+    }
+
+    pub fn dst(&self) -> Register {
+        self.dst
+    }
+
+    // None for `inp`, which gets its value from the program's input.
+    pub fn src(&self) -> Option<Source> {
+        self.src
+    }
+}
+
+fn parse(lines: Vec<String>) -> Result<Vec<Insn>, Box<dyn std::error::Error>> {
+    Ok(lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(n, l)| l.parse::<Insn>().map_err(|e| Located::new(n + 1, e)))
+        .collect::<Result<Vec<Insn>, Located<InsnParseError>>>()?)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct ALU {
+    w: isize,
+    x: isize,
+    y: isize,
+    z: isize,
+}
+
+impl ALU {
+    pub fn new() -> ALU {
+        ALU {
+            w: 0,
+            x: 0,
+            y: 0,
+            z: 0,
+        }
+    }
+
+    pub fn get(&self, reg: Register) -> isize {
+        match reg {
+            Register::W => self.w,
+            Register::X => self.x,
+            Register::Y => self.y,
+            Register::Z => self.z,
+        }
+    }
+
+    pub fn set(&mut self, reg: Register, val: isize) {
+        match reg {
+            Register::W => self.w = val,
+            Register::X => self.x = val,
+            Register::Y => self.y = val,
+            Register::Z => self.z = val,
+        }
+    }
+
+    fn get_source_val(&self, src: Source) -> isize {
+        match src {
+            Source::Immediate(val) => val,
+            Source::Register(reg) => self.get(reg),
+        }
+    }
+
+    // `inp` needs its value filled in as an immediate, which run_program() does.
+    pub fn execute(&mut self, insn: Insn) -> Result<(), AluError> {
+        let src_val = self.get_source_val(insn.src.ok_or(AluError::Input)?);
+        let dst_val = self.get(insn.dst);
+        match insn.op {
+            Operation::Inp => self.set(insn.dst, src_val),
+            Operation::Mul => self.set(insn.dst, dst_val * src_val),
+            Operation::Add => self.set(insn.dst, dst_val + src_val),
+            Operation::Mod => {
+                if dst_val < 0 || src_val <= 0 {
+                    return Err(AluError::Modulo(dst_val, src_val));
+                }
+                self.set(insn.dst, dst_val % src_val)
+            }
+            Operation::Div => {
+                if src_val == 0 {
+                    return Err(AluError::DivideByZero);
+                }
+                self.set(insn.dst, dst_val / src_val)
+            }
+            Operation::Eql => self.set(insn.dst, (dst_val == src_val) as isize),
+        }
+        Ok(())
+    }
+
+    // Run a whole program, feeding `inp` instructions from inputs in order.
+    pub fn run_program(&mut self, insns: &[Insn], inputs: &[isize]) -> Result<(), AluError> {
+        let mut inputs = inputs.iter();
+
+        for insn in insns {
+            if insn.op == Operation::Inp {
+                let src = inputs.next().map(|val| Source::Immediate(*val));
+                self.execute(Insn { src, ..*insn })?;
+            } else {
+                self.execute(*insn)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn number_to_digits(number: isize) -> Vec<isize> {
+    let as_string = number.to_string();
+    as_string
+        .chars()
+        .map(|c| c.to_digit(10).unwrap() as isize)
+        .collect::<Vec<isize>>()
+}
+
+fn digits_to_number(digits: &[isize]) -> isize {
+    digits.iter().enumerate().fold(0, |acc, (n, d)| {
+        acc + d * 10_isize.pow((digits.len() - n - 1) as u32)
+    })
+}
+
+// Part 1 wants the highest valid model number, part 2 wants the lowest.
+pub fn find_model(insns: &[Insn], highest: bool) -> Result<Option<isize>, OutOfBudget> {
+    let chunks = insns.split(|insn| insn.op == Operation::Inp);
+    let digits = insns
+        .iter()
+        .filter(|insn| insn.op == Operation::Inp)
+        .count();
+    let mut progress =
+        Progress::start("finding a model number", "digits processed").with_total(digits as u64);
+
+    // Maps state (value of z) to the best value it has been reached from.
+    let mut states: BTreeMap<isize, isize> = BTreeMap::new();
+    states.insert(0, 0);
+
+    let mut valid_models = vec![];
+
+    for chunk in chunks {
+        if chunk.is_empty() {
+            continue;
+        }
+
+        let mut new_bests: BTreeMap<isize, isize> = BTreeMap::new();
+
+        for (state, input) in states.iter() {
+            // There can be an awful lot of states by the last few digits.
+            progress.check()?;
+            for digit in 1..10 {
+                let mut alu = ALU {
+                    z: *state,
+                    x: 0,
+                    y: 0,
+                    w: digit,
+                };
+
+                // The puzzle says a crash means this digit is no good.
+                if alu.run_program(chunk, &[]).is_err() {
+                    continue;
+                }
+
+                let mut new_input = number_to_digits(*input);
+                new_input.push(digit);
+                let score = digits_to_number(&new_input);
+
+                if alu.z == 0 && new_input.len() == 14 {
+                    valid_models.push(score);
+                } else {
+                    if let Some(prev_score) = new_bests.get(&alu.z) {
+                        if (score > *prev_score) != highest {
+                            continue;
+                        }
+                    }
+                    new_bests.insert(alu.z, score);
+                }
+            }
+        }
+        states = new_bests;
+        progress.step()?;
+    }
+
+    Ok(if highest {
+        valid_models.into_iter().max()
+    } else {
+        valid_models.into_iter().min()
+    })
+}
+
+// What the puzzle's program does with each digit, give or take the three
+// numbers that change from one digit to the next.
+fn monad_block(divide: i64, check: i64, offset: i64) -> Vec<String> {
+    format!(
+        "inp w
+mul x 0
+add x z
+mod x 26
+div z {}
+add x {}
+eql x w
+eql x 0
+mul y 0
+add y 25
+mul y x
+add y 1
+mul z y
+mul y 0
+add y w
+add y {}
+mul y x
+add z y",
+        divide, check, offset
+    )
+    .lines()
+    .map(String::from)
+    .collect()
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: usize = 24;
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+    // The puzzle only has example programs, with no model numbers to find.
+    const EXAMPLES: &'static [Example] = &[];
+
+    type Input = Vec<Insn>;
+    type Part1 = isize;
+    type Part2 = isize;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input, Box<dyn std::error::Error>> {
+        parse(lines)
+    }
+
+    fn part1(insns: &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+        Ok(find_model(insns, true)?.ok_or(NoModelError)?)
+    }
+
+    fn part2(insns: &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+        Ok(find_model(insns, false)?.ok_or(NoModelError)?)
+    }
+
+    // A program shaped like the puzzle's.  Seven of the digits push the digit
+    // plus an offset onto z (in base 26), and the other seven pop one off and
+    // compare it with their own digit, in an order that never pops more than
+    // has been pushed.  Every comparison can come out equal for some pair of
+    // digits, so there's always a model number.  There's nothing for `size`
+    // to change.
+    fn generate(rng: &mut Rng, _size: Option<usize>) -> Vec<String> {
+        let mut program = vec![];
+        let mut pushes = 7;
+        let mut pushed = vec![];
+
+        while pushes > 0 || !pushed.is_empty() {
+            if pushes > 0 && (pushed.is_empty() || rng.chance(1, 2)) {
+                let offset = rng.between(1, 16);
+                // Checking against 10 or more always fails, so it always pushes.
+                program.extend(monad_block(1, rng.between(10, 16), offset));
+                pushed.push(offset);
+                pushes -= 1;
+            } else if let Some(offset) = pushed.pop() {
+                // How far the popping digit is from the pushing one.
+                let difference = rng.between(-8, 8);
+                program.extend(monad_block(26, difference - offset, rng.between(1, 16)));
+            }
+        }
+
+        program
+    }
+}
+
+pub fn run(source: Option<common::Source>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    common::run::<Day24>(source)
+}
+
+common::solution_tests!(Day24);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn example1_test() {
+        let example1 = "inp x
+mul x -1"
+            .lines()
+            .map(|l| l.to_string())
+            .collect::<Vec<String>>();
+
+        let insns = parse(example1).unwrap();
+
+        let mut alu = ALU::new();
+        alu.run_program(&insns, &[5]).unwrap();
+        assert!(alu.x == -5);
+
+        alu = ALU::new();
+        alu.run_program(&insns, &[-5]).unwrap();
+        assert!(alu.x == 5);
+    }
+
+    #[test]
+    fn example2_test() {
+        let example2 = "inp z
+inp x
+mul z 3
+eql z x"
+            .lines()
+            .map(|l| l.to_string())
+            .collect::<Vec<String>>();
+
+        let insns = parse(example2).unwrap();
+
+        let mut alu = ALU::new();
+        alu.run_program(&insns, &[9, 27]).unwrap();
+        assert!(alu.z == 1);
+    }
+
+    #[test]
+    fn example3_test() {
+        let example3 = "inp w
+add z w
+mod z 2
+div w 2
+add y w
+mod y 2
+div w 2
+add x w
+mod x 2
+div w 2
+mod w 2"
+            .lines()
+            .map(|l| l.to_string())
+            .collect::<Vec<String>>();
+
+        let insns = parse(example3).unwrap();
+
+        let mut alu = ALU::new();
+        alu.run_program(&insns, &[15]).unwrap();
+        assert!(alu.w == 1 && alu.x == 1 && alu.y == 1 && alu.z == 1);
+
+        alu = ALU::new();
+        alu.run_program(&insns, &[85]).unwrap();
+        assert!(alu.w == 0 && alu.x == 1 && alu.y == 0 && alu.z == 1);
+
+        alu = ALU::new();
+        alu.run_program(&insns, &[682]).unwrap();
+        assert!(alu.w == 1 && alu.x == 0 && alu.y == 1 && alu.z == 0);
+    }
+
+    #[test]
+    fn insn_test() {
+        let insn = "add x -1".parse::<Insn>().unwrap();
+
+        assert!(insn.op() == Operation::Add);
+        assert!(insn.dst() == Register::X);
+        assert!(insn.src() == Some(Source::Immediate(-1)));
+        assert!("inp w".parse::<Insn>().unwrap().src().is_none());
+        assert!("inp w 3".parse::<Insn>().is_err());
+        assert!("add x".parse::<Insn>().is_err());
+        assert!("sub x 1".parse::<Insn>().is_err());
+        assert!("add v 1".parse::<Insn>() == Err(InsnParseError::Register(5, "v".to_string())));
+        assert!("eql  x y2".parse::<Insn>() == Err(InsnParseError::Operand(8, "y2".to_string())));
+    }
+
+    #[test]
+    fn crash_test() {
+        let insns = parse(vec!["inp x".to_string(), "div x 0".to_string()]).unwrap();
+
+        assert!(ALU::new().run_program(&insns, &[1]) == Err(AluError::DivideByZero));
+        assert!(ALU::new().run_program(&insns, &[]) == Err(AluError::Input));
+    }
+
+    #[test]
+    fn digits_to_number_test() {
+        assert!(
+            digits_to_number(&vec![1, 3, 5, 7, 9, 2, 4, 6, 8, 9, 9, 9, 9, 9]) == 13579246899999
+        );
+        assert!(number_to_digits(13579246899999) == vec![1, 3, 5, 7, 9, 2, 4, 6, 8, 9, 9, 9, 9, 9]);
+    }
+
+    #[test]
+    fn budget_test() {
+        let insns = parse(Day24::generate(&mut Rng::new(0), None)).unwrap();
+
+        common::progress::set_budget(common::progress::Budget {
+            time: None,
+            steps: Some(3),
+        });
+        let e = find_model(&insns, true).unwrap_err();
+        common::progress::set_budget(Default::default());
+
+        assert!(e.update.steps == 4 && e.update.total == Some(14));
+    }
+}
@@ -0,0 +1,584 @@
+use common::image::Colour;
+use common::{Example, Grid, Rng, Simulation, Solution, Source, Visualise};
+
+// Each cell is empty, or a sea cucumber facing down (true) or right (false).
+type Map = Grid<Option<bool>>;
+
+fn parse(lines: Vec<String>) -> Result<Map, Box<dyn std::error::Error>> {
+    let cucumbers = Grid::parse(&lines, |c| match c {
+        'v' => Some(Some(true)),
+        '>' => Some(Some(false)),
+        '.' => Some(None),
+        _ => None,
+    })?;
+
+    Ok(cucumbers)
+}
+
+// Move every cucumber facing one way whose next cell (wrapping round the
+// edges) is empty.  They all look before any of them move.
+fn move_herd(map: &Map, down: bool) -> Map {
+    let mut updated = map.clone();
+    let (dx, dy) = if down { (0, 1) } else { (1, 0) };
+
+    for (x, y) in map.positions() {
+        if map[(x, y)] != Some(down) {
+            continue;
+        }
+
+        let (nx, ny) = ((x + dx) % map.width(), (y + dy) % map.height());
+        if map[(nx, ny)].is_none() {
+            updated[(nx, ny)] = Some(down);
+            updated[(x, y)] = None;
+        }
+    }
+
+    updated
+}
+
+pub fn step(map: &Map) -> Map {
+    // the easties go first, then the southies
+    move_herd(&move_herd(map, false), true)
+}
+
+pub fn find_stop_step(start: &Map) -> usize {
+    let mut i = 1;
+
+    let mut prev = start.clone();
+    loop {
+        let next = step(&prev);
+
+        if prev == next {
+            return i;
+        }
+
+        prev = next;
+        i += 1;
+    }
+}
+
+// The herds shuffling along until none of them can move.
+pub struct Herds {
+    map: Map,
+    steps: usize,
+    stopped: bool,
+}
+
+impl Simulation for Herds {
+    fn step(&mut self) -> bool {
+        if self.stopped {
+            return false;
+        }
+        let next = step(&self.map);
+        self.stopped = next == self.map;
+        self.map = next;
+        self.steps += 1;
+
+        true
+    }
+
+    fn render(&self) -> String {
+        let caption = if self.stopped {
+            format!("step {}, and nobody moved", self.steps)
+        } else {
+            format!("step {}", self.steps)
+        };
+        let map = self.map.render(|&c| match c {
+            Some(true) => 'v',
+            Some(false) => '>',
+            None => '.',
+        });
+
+        format!("{}\n{}", caption, map)
+    }
+
+    // The east herd in orange and the south herd in green, on the sea floor.
+    fn picture(&self) -> Option<Grid<Colour>> {
+        Some(self.map.map(|&c| match c {
+            Some(true) => Colour(60, 200, 120),
+            Some(false) => Colour(240, 130, 40),
+            None => Colour(10, 30, 60),
+        }))
+    }
+}
+
+const EXAMPLE1: &str = "v...>>.vv>
+.vv>>.vv..
+>>.>v>...v
+>>v>>.>.v.
+v>v.vv.v..
+>.>>..v...
+.vv..>.>v.
+v.v..>>v.v
+....v..v.>";
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: usize = 25;
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE1,
+        part1: Some("58"),
+        part2: None,
+    }];
+
+    type Input = Map;
+    type Part1 = usize;
+    type Part2 = &'static str;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input, Box<dyn std::error::Error>> {
+        parse(lines)
+    }
+
+    fn part1(cucumbers: &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+        Ok(find_stop_step(cucumbers))
+    }
+
+    // There's no puzzle for the 50th star, you get it for finishing the other 49.
+    fn part2(_cucumbers: &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+        Ok("Merry Christmas!")
+    }
+
+    // A `size` x `size` sea floor.  A random herd can go round and round
+    // forever, so one column of south-facing cucumbers and one row of
+    // east-facing ones are packed too tight to ever move, which walls the rest
+    // in so they have to stop eventually.
+    fn generate(rng: &mut Rng, size: Option<usize>) -> Vec<String> {
+        let size = size.unwrap_or(139).max(1);
+        let (wall_x, wall_y) = (rng.below(size), rng.below(size));
+
+        (0..size)
+            .map(|y| {
+                (0..size)
+                    .map(|x| match (x == wall_x, y == wall_y) {
+                        (true, _) => 'v',
+                        (_, true) => '>',
+                        _ => *rng.choose(&['.', '.', '>', 'v']),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl Visualise for Day25 {
+    fn simulation(cucumbers: &Self::Input) -> Box<dyn Simulation> {
+        Box::new(Herds {
+            map: cucumbers.clone(),
+            steps: 0,
+            stopped: false,
+        })
+    }
+}
+
+pub fn run(source: Option<Source>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    common::run::<Day25>(source)
+}
+
+common::solution_tests!(Day25);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE1_1: &str = "....>.>v.>
+v.v>.>v.v.
+>v>>..>v..
+>>v>v>.>.v
+.>v.v...v.
+v>>.>vvv..
+..v...>>..
+vv...>>vv.
+>.v.v..v.v";
+
+    const EXAMPLE1_2: &str = ">.v.v>>..v
+v.v.>>vv..
+>v>.>.>.v.
+>>v>v.>v>.
+.>..v....v
+.>v>>.v.v.
+v....v>v>.
+.vv..>>v..
+v>.....vv.";
+
+    const EXAMPLE1_3: &str = "v>v.v>.>v.
+v...>>.v.v
+>vv>.>v>..
+>>v>v.>.v>
+..>....v..
+.>.>v>v..v
+..v..v>vv>
+v.v..>>v..
+.v>....v..";
+
+    const EXAMPLE1_4: &str = "v>..v.>>..
+v.v.>.>.v.
+>vv.>>.v>v
+>>.>..v>.>
+..v>v...v.
+..>>.>vv..
+>.v.vv>v.v
+.....>>vv.
+vvv>...v..";
+
+    const EXAMPLE1_5: &str = "vv>...>v>.
+v.v.v>.>v.
+>.v.>.>.>v
+>v>.>..v>>
+..v>v.v...
+..>.>>vvv.
+.>...v>v..
+..v.v>>v.v
+v.v.>...v.";
+
+    const EXAMPLE1_10: &str = "..>..>>vv.
+v.....>>.v
+..v.v>>>v>
+v>.>v.>>>.
+..v>v.vv.v
+.v.>>>.v..
+v.v..>v>..
+..v...>v.>
+.vv..v>vv.";
+
+    const EXAMPLE1_20: &str = "v>.....>>.
+>vv>.....v
+.>v>v.vv>>
+v>>>v.>v.>
+....vv>v..
+.v.>>>vvv.
+..v..>>vv.
+v.v...>>.v
+..v.....v>";
+
+    const EXAMPLE1_30: &str = ".vv.v..>>>
+v>...v...>
+>.v>.>vv.>
+>v>.>.>v.>
+.>..v.vv..
+..v>..>>v.
+....v>..>v
+v.v...>vv>
+v.v...>vvv";
+
+    const EXAMPLE1_40: &str = ">>v>v..v..
+..>>v..vv.
+..>>>v.>.v
+..>>>>vvv>
+v.....>...
+v.v...>v>>
+>vv.....v>
+.>v...v.>v
+vvv.v..v.>";
+
+    const EXAMPLE1_50: &str = "..>>v>vv.v
+..v.>>vv..
+v.>>v>>v..
+..>>>>>vv.
+vvv....>vv
+..v....>>>
+v>.......>
+.vv>....v>
+.>v.vv.v..";
+
+    const EXAMPLE1_55: &str = "..>>v>vv..
+..v.>>vv..
+..>>v>>vv.
+..>>>>>vv.
+v......>vv
+v>v....>>v
+vvv...>..>
+>vv.....>.
+.>v.vv.v..";
+
+    const EXAMPLE1_56: &str = "..>>v>vv..
+..v.>>vv..
+..>>v>>vv.
+..>>>>>vv.
+v......>vv
+v>v....>>v
+vvv....>.>
+>vv......>
+.>v.vv.v..";
+
+    const EXAMPLE1_57: &str = "..>>v>vv..
+..v.>>vv..
+..>>v>>vv.
+..>>>>>vv.
+v......>vv
+v>v....>>v
+vvv.....>>
+>vv......>
+.>v.vv.v..";
+
+    const EXAMPLE1_58: &str = "..>>v>vv..
+..v.>>vv..
+..>>v>>vv.
+..>>>>>vv.
+v......>vv
+v>v....>>v
+vvv.....>>
+>vv......>
+.>v.vv.v..";
+
+    #[test]
+    fn basic_test() {
+        let start = "...>>>>>...".to_string();
+        let step1_str = "...>>>>.>..".to_string();
+        let step2_str = "...>>>.>.>.".to_string();
+        let cucumbers = parse(vec![start]).unwrap();
+
+        let step1 = step(&cucumbers);
+        let step2 = step(&step1);
+        assert!(step1 == parse(vec![step1_str]).unwrap());
+        assert!(step2 == parse(vec![step2_str]).unwrap());
+    }
+
+    #[test]
+    fn move_both_test() {
+        let example = "..........
+.>v....v..
+.......>..
+.........."
+            .lines()
+            .map(|l| l.to_string())
+            .collect::<Vec<String>>();
+
+        let step1_str = "..........
+.>........
+..v....v>.
+.........."
+            .lines()
+            .map(|l| l.to_string())
+            .collect::<Vec<String>>();
+
+        let cucumbers = parse(example).unwrap();
+
+        let step1 = step(&cucumbers);
+        assert!(step1 == parse(step1_str).unwrap());
+    }
+
+    #[test]
+    fn wrap_test() {
+        let example = "...>...
+.......
+......>
+v.....>
+......>
+.......
+..vvv.."
+            .lines()
+            .map(|l| l.to_string())
+            .collect::<Vec<String>>();
+
+        let step1_str = "..vv>..
+.......
+>......
+v.....>
+>......
+.......
+....v.."
+            .lines()
+            .map(|l| l.to_string())
+            .collect::<Vec<String>>();
+
+        let step2_str = "....v>.
+..vv...
+.>.....
+......>
+v>.....
+.......
+......."
+            .lines()
+            .map(|l| l.to_string())
+            .collect::<Vec<String>>();
+
+        let step3_str = "......>
+..v.v..
+..>v...
+>......
+..>....
+v......
+......."
+            .lines()
+            .map(|l| l.to_string())
+            .collect::<Vec<String>>();
+
+        let step4_str = ">......
+..v....
+..>.v..
+.>.v...
+...>...
+.......
+v......"
+            .lines()
+            .map(|l| l.to_string())
+            .collect::<Vec<String>>();
+
+        let cucumbers = parse(example).unwrap();
+
+        let step1 = step(&cucumbers);
+        let step2 = step(&step1);
+        let step3 = step(&step2);
+        let step4 = step(&step3);
+        assert!(step1 == parse(step1_str).unwrap());
+        assert!(step2 == parse(step2_str).unwrap());
+        assert!(step3 == parse(step3_str).unwrap());
+        assert!(step4 == parse(step4_str).unwrap());
+    }
+
+    #[test]
+    fn find_stop_test() {
+        let cucumbers = parse(
+            EXAMPLE1
+                .lines()
+                .map(|l| l.to_string())
+                .collect::<Vec<String>>(),
+        )
+        .unwrap();
+        let step1 = parse(
+            EXAMPLE1_1
+                .lines()
+                .map(|l| l.to_string())
+                .collect::<Vec<String>>(),
+        )
+        .unwrap();
+        let step2 = parse(
+            EXAMPLE1_2
+                .lines()
+                .map(|l| l.to_string())
+                .collect::<Vec<String>>(),
+        )
+        .unwrap();
+        let step3 = parse(
+            EXAMPLE1_3
+                .lines()
+                .map(|l| l.to_string())
+                .collect::<Vec<String>>(),
+        )
+        .unwrap();
+        let step4 = parse(
+            EXAMPLE1_4
+                .lines()
+                .map(|l| l.to_string())
+                .collect::<Vec<String>>(),
+        )
+        .unwrap();
+        let step5 = parse(
+            EXAMPLE1_5
+                .lines()
+                .map(|l| l.to_string())
+                .collect::<Vec<String>>(),
+        )
+        .unwrap();
+        let step10 = parse(
+            EXAMPLE1_10
+                .lines()
+                .map(|l| l.to_string())
+                .collect::<Vec<String>>(),
+        )
+        .unwrap();
+        let step20 = parse(
+            EXAMPLE1_20
+                .lines()
+                .map(|l| l.to_string())
+                .collect::<Vec<String>>(),
+        )
+        .unwrap();
+        let step30 = parse(
+            EXAMPLE1_30
+                .lines()
+                .map(|l| l.to_string())
+                .collect::<Vec<String>>(),
+        )
+        .unwrap();
+        let step40 = parse(
+            EXAMPLE1_40
+                .lines()
+                .map(|l| l.to_string())
+                .collect::<Vec<String>>(),
+        )
+        .unwrap();
+        let step50 = parse(
+            EXAMPLE1_50
+                .lines()
+                .map(|l| l.to_string())
+                .collect::<Vec<String>>(),
+        )
+        .unwrap();
+        let step55 = parse(
+            EXAMPLE1_55
+                .lines()
+                .map(|l| l.to_string())
+                .collect::<Vec<String>>(),
+        )
+        .unwrap();
+        let step56 = parse(
+            EXAMPLE1_56
+                .lines()
+                .map(|l| l.to_string())
+                .collect::<Vec<String>>(),
+        )
+        .unwrap();
+        let step57 = parse(
+            EXAMPLE1_57
+                .lines()
+                .map(|l| l.to_string())
+                .collect::<Vec<String>>(),
+        )
+        .unwrap();
+        let step58 = parse(
+            EXAMPLE1_58
+                .lines()
+                .map(|l| l.to_string())
+                .collect::<Vec<String>>(),
+        )
+        .unwrap();
+
+        let mut i = 1;
+        let mut prev = cucumbers.clone();
+        loop {
+            let next = step(&prev);
+            match i {
+                1 => assert!(next == step1),
+                2 => assert!(next == step2),
+                3 => assert!(next == step3),
+                4 => assert!(next == step4),
+                5 => assert!(next == step5),
+                10 => assert!(next == step10),
+                20 => assert!(next == step20),
+                30 => assert!(next == step30),
+                40 => assert!(next == step40),
+                50 => assert!(next == step50),
+                55 => assert!(next == step55),
+                56 => assert!(next == step56),
+                57 => assert!(next == step57),
+                58 => assert!(next == step58),
+                _ => {}
+            }
+            if next == prev {
+                break;
+            }
+            prev = next;
+            i += 1;
+        }
+        assert!(i == 58);
+        assert!(find_stop_step(&cucumbers) == 58);
+    }
+
+    #[test]
+    fn simulation_test() {
+        let cucumbers = parse(EXAMPLE1.lines().map(String::from).collect()).unwrap();
+        let mut player = common::visualise::Player::new(Day25::simulation(&cucumbers));
+
+        assert!(player.frame().starts_with("step 0\nv...>>.vv>\n"));
+        assert!(player.jump(100) == 58);
+        assert!(player.frame().starts_with("step 58, and nobody moved\n"));
+        assert!(player.frame().ends_with(EXAMPLE1_58));
+
+        let picture = Day25::simulation(&cucumbers).picture().unwrap();
+        assert!((picture.width(), picture.height()) == (10, 9));
+        assert!(picture[(0, 0)] == Colour(60, 200, 120));
+    }
+}
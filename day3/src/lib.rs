@@ -0,0 +1,507 @@
+use common::{Example, Located, Rng, Solution, Source};
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq)]
+pub enum ReportParseError {
+    Empty,
+    Blank,
+    // column, and what was there
+    Bit(usize, char),
+    // how wide the first reading was, and how wide this one is
+    Width(usize, usize),
+}
+
+impl std::error::Error for ReportParseError {}
+
+impl std::fmt::Display for ReportParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ReportParseError::Empty => write!(f, "empty diagnostic report"),
+            ReportParseError::Blank => write!(f, "blank line"),
+            ReportParseError::Bit(column, c) => {
+                write!(f, "\"{}\" at column {} isn't a 0 or 1", c, column)
+            }
+            ReportParseError::Width(expected, found) => write!(
+                f,
+                "reading is {} bits wide, but the first one was {}",
+                found, expected
+            ),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RatingError {
+    Empty,
+    // readings of different widths, which can't be compared bit by bit
+    Ragged,
+    // which bit (counting from 1) left nothing to pick from
+    Emptied(usize),
+    // how many readings were still left after the last bit
+    Undecided(usize),
+}
+
+impl std::error::Error for RatingError {}
+
+impl std::fmt::Display for RatingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RatingError::Empty => write!(f, "no readings to rate"),
+            RatingError::Ragged => write!(f, "readings aren't all the same width"),
+            RatingError::Emptied(bit) => write!(f, "no readings left after bit {}", bit),
+            RatingError::Undecided(left) => {
+                write!(f, "{} readings were still left after every bit", left)
+            }
+        }
+    }
+}
+
+// A line of the report, most significant bit first.  It's kept as bits
+// rather than a u16 so that it can be as wide as the report likes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reading(Vec<bool>);
+
+impl Reading {
+    pub fn width(&self) -> usize {
+        self.0.len()
+    }
+
+    // Column 0 is the most significant bit.
+    pub fn bit(&self, column: usize) -> bool {
+        self.0[column]
+    }
+
+    // Every bit flipped, which is how epsilon falls out of gamma.
+    pub fn inverse(&self) -> Reading {
+        Reading(self.0.iter().map(|bit| !bit).collect())
+    }
+
+    // The reading as a number, if it fits in one.
+    pub fn value(&self) -> Option<u64> {
+        self.0.iter().try_fold(0u64, |value, &bit| {
+            value.checked_mul(2)?.checked_add(bit as u64)
+        })
+    }
+
+    // 32 bits at a time, least significant first, for multiplying.
+    fn limbs(&self) -> Vec<u32> {
+        self.0
+            .rchunks(32)
+            .map(|chunk| chunk.iter().fold(0, |limb, &bit| limb << 1 | bit as u32))
+            .collect()
+    }
+}
+
+impl FromStr for Reading {
+    type Err = ReportParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        if line.is_empty() {
+            return Err(ReportParseError::Blank);
+        }
+
+        line.chars()
+            .enumerate()
+            .map(|(n, c)| match c {
+                '0' => Ok(false),
+                '1' => Ok(true),
+                _ => Err(ReportParseError::Bit(n + 1, c)),
+            })
+            .collect::<Result<Vec<bool>, _>>()
+            .map(Reading)
+    }
+}
+
+impl std::fmt::Display for Reading {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for &bit in &self.0 {
+            write!(f, "{}", if bit { '1' } else { '0' })?;
+        }
+        Ok(())
+    }
+}
+
+// The width is whatever the first reading says, and the rest have to agree.
+fn parse(lines: Vec<String>) -> Result<Vec<Reading>, Box<dyn std::error::Error>> {
+    let mut report: Vec<Reading> = vec![];
+
+    // as before, we're treating parsing separately from solution logic
+    for (n, line) in lines.iter().enumerate() {
+        let reading = line
+            .parse::<Reading>()
+            .map_err(|e| Located::new(n + 1, e))?;
+        if let Some(first) = report.first() {
+            if reading.width() != first.width() {
+                let e = ReportParseError::Width(first.width(), reading.width());
+                return Err(Located::new(n + 1, e).into());
+            }
+        }
+        report.push(reading);
+    }
+
+    if report.is_empty() {
+        return Err(ReportParseError::Empty.into());
+    }
+    Ok(report)
+}
+
+pub fn width(report: &[Reading]) -> Result<usize, RatingError> {
+    let width = report.first().ok_or(RatingError::Empty)?.width();
+
+    match report.iter().all(|reading| reading.width() == width) {
+        true => Ok(width),
+        false => Err(RatingError::Ragged),
+    }
+}
+
+// How many readings have a 1 or a 0 in a column.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColumnStats {
+    pub ones: usize,
+    pub zeros: usize,
+}
+
+// Counts for every column, most significant first.
+pub fn column_stats(report: &[Reading]) -> Result<Vec<ColumnStats>, RatingError> {
+    let readings: Vec<&Reading> = report.iter().collect();
+
+    Ok((0..width(report)?)
+        .map(|column| stats(&readings, column))
+        .collect())
+}
+
+fn stats(readings: &[&Reading], column: usize) -> ColumnStats {
+    let ones = readings
+        .iter()
+        .filter(|reading| reading.bit(column))
+        .count();
+
+    ColumnStats {
+        ones,
+        zeros: readings.len() - ones,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Keep {
+    MostCommon,
+    LeastCommon,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Order {
+    MsbFirst,
+    LsbFirst,
+}
+
+// Which bit to go for in each column, and which one to go for when there are
+// as many ones as zeros.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BitCriteria {
+    pub keep: Keep,
+    pub tie: bool,
+    pub order: Order,
+}
+
+impl BitCriteria {
+    pub const GAMMA: BitCriteria = BitCriteria::new(Keep::MostCommon, false);
+    // Epsilon is gamma with every bit flipped, ties included.
+    pub const EPSILON: BitCriteria = BitCriteria::new(Keep::LeastCommon, true);
+    pub const OXYGEN_GENERATOR: BitCriteria = BitCriteria::new(Keep::MostCommon, true);
+    pub const CO2_SCRUBBER: BitCriteria = BitCriteria::new(Keep::LeastCommon, false);
+
+    pub const fn new(keep: Keep, tie: bool) -> BitCriteria {
+        BitCriteria {
+            keep,
+            tie,
+            order: Order::MsbFirst,
+        }
+    }
+
+    pub fn lsb_first(self) -> BitCriteria {
+        BitCriteria {
+            order: Order::LsbFirst,
+            ..self
+        }
+    }
+
+    pub fn pick(&self, stats: ColumnStats) -> bool {
+        if stats.ones == stats.zeros {
+            return self.tie;
+        }
+        let ones_common = stats.ones > stats.zeros;
+
+        match self.keep {
+            Keep::MostCommon => ones_common,
+            Keep::LeastCommon => !ones_common,
+        }
+    }
+
+    // The bit picked from every column, like gamma and epsilon.
+    pub fn rate(&self, stats: &[ColumnStats]) -> Reading {
+        Reading(stats.iter().map(|&column| self.pick(column)).collect())
+    }
+
+    fn columns(&self, width: usize) -> Box<dyn Iterator<Item = usize>> {
+        match self.order {
+            Order::MsbFirst => Box::new(0..width),
+            Order::LsbFirst => Box::new((0..width).rev()),
+        }
+    }
+
+    // Keep the readings with the picked bit in each column in turn, counting
+    // again each time, until there's only one left, like the oxygen generator
+    // and CO2 scrubber ratings.
+    pub fn filter(&self, report: &[Reading]) -> Result<Reading, RatingError> {
+        let mut filter: Vec<&Reading> = report.iter().collect();
+
+        for column in self.columns(width(report)?) {
+            // Stop as soon as there's one left, before its bits get a say.
+            if filter.len() == 1 {
+                break;
+            }
+            let bit = self.pick(stats(&filter, column));
+            filter.retain(|reading| reading.bit(column) == bit);
+
+            if filter.is_empty() {
+                return Err(RatingError::Emptied(column + 1));
+            }
+        }
+
+        match filter[..] {
+            [reading] => Ok(reading.clone()),
+            _ => Err(RatingError::Undecided(filter.len())),
+        }
+    }
+}
+
+pub fn get_oxygen_generator_rating(report: &[Reading]) -> Result<Reading, RatingError> {
+    BitCriteria::OXYGEN_GENERATOR.filter(report)
+}
+
+pub fn get_co2_scrubber_rating(report: &[Reading]) -> Result<Reading, RatingError> {
+    BitCriteria::CO2_SCRUBBER.filter(report)
+}
+
+// Both parts' answers are two readings multiplied together, which can be as
+// wide as the two readings put together.  Kept as 32 bit limbs, least
+// significant first, so that a limb times a limb fits in a u64.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Product(Vec<u32>);
+
+fn multiply(a: &Reading, b: &Reading) -> Product {
+    let (a, b) = (a.limbs(), b.limbs());
+    let mut product = vec![0u32; a.len() + b.len()];
+
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            let sum = x as u64 * y as u64 + product[i + j] as u64 + carry;
+            product[i + j] = sum as u32;
+            carry = sum >> 32;
+        }
+        product[i + b.len()] = carry as u32;
+    }
+    while product.last() == Some(&0) {
+        product.pop();
+    }
+
+    Product(product)
+}
+
+impl std::fmt::Display for Product {
+    // Nine decimal digits at a time, by long division of the limbs.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        const BILLION: u64 = 1_000_000_000;
+        let mut limbs = self.0.clone();
+        let mut chunks = vec![];
+
+        while !limbs.is_empty() {
+            let mut remainder = 0u64;
+            for limb in limbs.iter_mut().rev() {
+                let value = remainder << 32 | *limb as u64;
+                *limb = (value / BILLION) as u32;
+                remainder = value % BILLION;
+            }
+            chunks.push(remainder);
+            while limbs.last() == Some(&0) {
+                limbs.pop();
+            }
+        }
+
+        match chunks.split_last() {
+            None => write!(f, "0"),
+            Some((first, rest)) => {
+                write!(f, "{}", first)?;
+                rest.iter()
+                    .rev()
+                    .try_for_each(|chunk| write!(f, "{:09}", chunk))
+            }
+        }
+    }
+}
+
+const EXAMPLE: &str = "00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010";
+
+// How wide the puzzle's own readings are, for generating more like them.
+const DIAG_LEN: usize = 12;
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: usize = 3;
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("198"),
+        part2: Some("230"),
+    }];
+
+    type Input = Vec<Reading>;
+    type Part1 = Product;
+    type Part2 = Product;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input, Box<dyn std::error::Error>> {
+        parse(lines)
+    }
+
+    fn part1(report: &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+        let stats = column_stats(report)?;
+        let gamma_rate = BitCriteria::GAMMA.rate(&stats);
+        let epsilon_rate = BitCriteria::EPSILON.rate(&stats);
+        let power_consumption = multiply(&gamma_rate, &epsilon_rate);
+
+        let ones: Vec<String> = stats.iter().map(|c| c.ones.to_string()).collect();
+        common::note("ones_per_bit", ones.join(","));
+        common::note("most_common", &gamma_rate);
+        // In decimal when they fit, but most_common has gamma either way.
+        if let (Some(gamma), Some(epsilon)) = (gamma_rate.value(), epsilon_rate.value()) {
+            common::note("gamma_rate", gamma);
+            common::note("epsilon_rate", epsilon);
+        }
+
+        Ok(power_consumption)
+    }
+
+    fn part2(report: &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+        let oxygen_generator_rating = get_oxygen_generator_rating(report)?;
+        let co2_scrubber_rating = get_co2_scrubber_rating(report)?;
+        let life_support_rating = multiply(&oxygen_generator_rating, &co2_scrubber_rating);
+
+        common::note("oxygen_generator_rating", &oxygen_generator_rating);
+        common::note("co2_scrubber_rating", &co2_scrubber_rating);
+
+        Ok(life_support_rating)
+    }
+
+    // `size` readings.
+    fn generate(rng: &mut Rng, size: Option<usize>) -> Vec<String> {
+        (0..size.unwrap_or(1000))
+            .map(|_| format!("{:0width$b}", rng.below(1 << DIAG_LEN), width = DIAG_LEN))
+            .collect()
+    }
+}
+
+pub fn run(source: Option<Source>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    common::run::<Day3>(source)
+}
+
+common::solution_tests!(Day3);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn report(lines: &[&str]) -> Result<Vec<Reading>, String> {
+        parse(lines.iter().map(|l| l.to_string()).collect()).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn width_test() {
+        let e = report(&["101", "1101"]).unwrap_err();
+        assert!(e == "line 2: reading is 4 bits wide, but the first one was 3");
+        assert!(report(&["10", "1x"]).unwrap_err() == "line 2: \"x\" at column 2 isn't a 0 or 1");
+
+        let ragged = ["01", "110"].map(|l| l.parse::<Reading>().unwrap());
+        assert!(get_oxygen_generator_rating(&ragged) == Err(RatingError::Ragged));
+        assert!(get_co2_scrubber_rating(&[]) == Err(RatingError::Empty));
+    }
+
+    #[test]
+    fn wide_test() {
+        // 70 bits, which a u64 has no room for.
+        let readings = report(&[&"0".repeat(70), &"1".repeat(70)]).unwrap();
+
+        let oxygen = get_oxygen_generator_rating(&readings).unwrap();
+        let co2 = get_co2_scrubber_rating(&readings).unwrap();
+        assert!(oxygen.width() == 70 && oxygen.value().is_none());
+        assert!(co2.value() == Some(0));
+
+        assert!(Day3::part2(&readings).unwrap().to_string() == "0");
+
+        // (2^70 - 1) * (2^69 - 1), which needs 139 bits.
+        let (a, b) = ("1".repeat(70), "1".repeat(69));
+        let product = multiply(&a.parse().unwrap(), &b.parse().unwrap());
+        assert!(product.to_string() == "696898287454081973171220308589185180106753");
+        let small = multiply(&"1101".parse().unwrap(), &"1000000000".parse().unwrap());
+        assert!(small.to_string() == "6656");
+    }
+
+    #[test]
+    fn undecided_test() {
+        let twins = report(&["101", "101", "000"]).unwrap();
+
+        assert!(get_oxygen_generator_rating(&twins) == Err(RatingError::Undecided(2)));
+        assert!(get_co2_scrubber_rating(&twins).unwrap().to_string() == "000");
+
+        // Nobody has a 1 to start with, so the least common bit is nobody's.
+        let zeros = report(&["00", "01"]).unwrap();
+        assert!(get_oxygen_generator_rating(&zeros).unwrap().to_string() == "01");
+        assert!(get_co2_scrubber_rating(&zeros) == Err(RatingError::Emptied(1)));
+    }
+
+    #[test]
+    fn single_test() {
+        // One reading is already the rating, whatever its bits say.
+        let one = report(&["1"]).unwrap();
+
+        assert!(get_co2_scrubber_rating(&one).unwrap().to_string() == "1");
+        assert!(get_oxygen_generator_rating(&one).unwrap().to_string() == "1");
+        assert!(Day3::part2(&one).unwrap().to_string() == "1");
+    }
+
+    #[test]
+    fn criteria_test() {
+        let readings = report(&EXAMPLE.lines().collect::<Vec<&str>>()).unwrap();
+        let stats = column_stats(&readings).unwrap();
+
+        let ones: Vec<usize> = stats.iter().map(|c| c.ones).collect();
+        assert!(ones == vec![7, 5, 8, 7, 5]);
+        assert!(stats.iter().all(|c| c.ones + c.zeros == 12));
+
+        let gamma = BitCriteria::GAMMA.rate(&stats);
+        assert!(gamma.to_string() == "10110");
+        assert!(BitCriteria::EPSILON.rate(&stats) == gamma.inverse());
+
+        // From the other end, and breaking ties the other way.
+        let oxygen = BitCriteria::OXYGEN_GENERATOR.lsb_first();
+        assert!(oxygen.filter(&readings).unwrap().to_string() == "11110");
+        let oxygen = BitCriteria::new(Keep::MostCommon, false);
+        assert!(oxygen.filter(&readings).unwrap().to_string() == "10110");
+
+        let split = ColumnStats { ones: 2, zeros: 2 };
+        assert!(BitCriteria::new(Keep::LeastCommon, true).pick(split));
+        assert!(!BitCriteria::new(Keep::MostCommon, false).pick(split));
+    }
+}
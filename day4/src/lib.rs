@@ -0,0 +1,513 @@
+use common::{Example, Located, Rng, Solution, Source};
+
+#[derive(Debug, PartialEq)]
+pub enum BingoError {
+    Empty,
+    // column, and what was there
+    Number(usize, String),
+    // how many numbers the row had, and how many the first row did
+    Row(usize, usize),
+    // how many rows the board had, and how many numbers were in each
+    Board(usize, usize),
+    NoBoards,
+    NoWinner,
+}
+
+impl std::error::Error for BingoError {}
+
+impl std::fmt::Display for BingoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            BingoError::Empty => write!(f, "no numbers to draw"),
+            BingoError::Number(column, s) => {
+                write!(f, "\"{}\" at column {} isn't a bingo number", s, column)
+            }
+            BingoError::Row(count, first) => write!(
+                f,
+                "row has {} numbers, but the board's first row has {}",
+                count, first
+            ),
+            BingoError::Board(rows, columns) => write!(
+                f,
+                "board has {} rows of {} numbers, but it should be square",
+                rows, columns
+            ),
+            BingoError::NoBoards => write!(f, "no boards to play"),
+            BingoError::NoWinner => write!(f, "no board ever wins"),
+        }
+    }
+}
+
+fn parse_numbers<'a>(
+    line: &str,
+    numbers: impl Iterator<Item = &'a str>,
+) -> Result<Vec<u8>, BingoError> {
+    numbers
+        .map(|x| {
+            x.parse::<u8>()
+                .map_err(|_| BingoError::Number(common::parse::column(line, x), x.to_string()))
+        })
+        .collect()
+}
+
+// A board of any size, as long as it's square.  The numbers are kept
+// flattened, a row at a time, since there's no 2D Vec to hand.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BingoBoard {
+    size: usize,
+    numbers: Vec<u8>,
+}
+
+impl BingoBoard {
+    pub fn new(rows: Vec<Vec<u8>>) -> Result<BingoBoard, BingoError> {
+        let size = rows.len();
+
+        if let Some(row) = rows.iter().find(|row| row.len() != rows[0].len()) {
+            return Err(BingoError::Row(row.len(), rows[0].len()));
+        }
+        match rows.first() {
+            Some(row) if row.len() == size => Ok(BingoBoard {
+                size,
+                numbers: rows.concat(),
+            }),
+            row => Err(BingoError::Board(size, row.map_or(0, |row| row.len()))),
+        }
+    }
+
+    // How many numbers across (and down) it is.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn numbers(&self) -> &[u8] {
+        &self.numbers
+    }
+
+    // Whether marking `cell` finished a line through it.
+    fn completes(&self, marked: &[bool], cell: usize, rules: Rules) -> bool {
+        let n = self.size;
+        let (row, column) = (cell / n, cell % n);
+        let line = |start: usize, step: usize| (0..n).all(|k| marked[start + k * step]);
+
+        line(row * n, 1)
+            || line(column, n)
+            || (rules.diagonals && row == column && line(0, n + 1))
+            || (rules.diagonals && row + column == n - 1 && line(n - 1, n - 1))
+    }
+
+    // The cells of every line that wins, by index.
+    fn lines(&self, rules: Rules) -> Vec<Vec<usize>> {
+        let n = self.size;
+        let line = |start: usize, step: usize| (0..n).map(|k| start + k * step).collect();
+
+        let mut lines: Vec<Vec<usize>> =
+            (0..n).flat_map(|i| [line(i * n, 1), line(i, n)]).collect();
+        if rules.diagonals {
+            lines.extend([line(0, n + 1), line(n - 1, n - 1)]);
+        }
+        lines
+    }
+
+    // Whether any line is made up of numbers that get drawn, in whatever order
+    // they come out.
+    pub fn can_win(&self, draw_order: &[u8], rules: Rules) -> bool {
+        let mut drawn = [false; 256];
+        for &x in draw_order {
+            drawn[x as usize] = true;
+        }
+
+        self.lines(rules)
+            .iter()
+            .any(|line| line.iter().all(|&cell| drawn[self.numbers[cell] as usize]))
+    }
+
+    // The sum of the numbers that aren't marked.
+    fn unmarked(&self, marked: &[bool]) -> u64 {
+        self.numbers
+            .iter()
+            .zip(marked)
+            .filter(|(_, &marked)| !marked)
+            .map(|(&x, _)| x as u64)
+            .sum()
+    }
+}
+
+// Anything that changes how the game's played.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Rules {
+    // a diagonal through the middle counts as a line too
+    pub diagonals: bool,
+}
+
+// A board winning, and what it scored.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Win {
+    // the board's index in the input, counting from 0
+    pub board: usize,
+    pub draw: u8,
+    // how many numbers had been drawn before this one
+    pub draw_index: usize,
+    pub score: u64,
+}
+
+// Plays a set of boards, a draw at a time.  Every number knows where it is on
+// every board, so a draw only looks at the boards it's on.
+pub struct BingoGame<'a> {
+    boards: &'a [BingoBoard],
+    rules: Rules,
+    // (board, cell) for every place each number is
+    places: Vec<Vec<(usize, usize)>>,
+    marked: Vec<Vec<bool>>,
+    won: Vec<bool>,
+    drawn: usize,
+}
+
+impl<'a> BingoGame<'a> {
+    pub fn new(boards: &'a [BingoBoard], rules: Rules) -> BingoGame<'a> {
+        let mut places = vec![vec![]; 256];
+        for (b, board) in boards.iter().enumerate() {
+            for (cell, &x) in board.numbers.iter().enumerate() {
+                places[x as usize].push((b, cell));
+            }
+        }
+
+        BingoGame {
+            boards,
+            rules,
+            places,
+            marked: boards
+                .iter()
+                .map(|b| vec![false; b.numbers.len()])
+                .collect(),
+            won: vec![false; boards.len()],
+            drawn: 0,
+        }
+    }
+
+    // Mark a number everywhere it is, and return the boards that won because
+    // of it in the order they came in.  A board only wins once.
+    pub fn draw(&mut self, draw: u8) -> Vec<Win> {
+        let mut winners: Vec<usize> = vec![];
+
+        // Places are in board order, so the winners are too.
+        for &(b, cell) in &self.places[draw as usize] {
+            self.marked[b][cell] = true;
+            if !self.won[b] && self.boards[b].completes(&self.marked[b], cell, self.rules) {
+                self.won[b] = true;
+                winners.push(b);
+            }
+        }
+        let draw_index = self.drawn;
+        self.drawn += 1;
+
+        // A number can be on a board more than once, so the scores wait
+        // until it's been marked everywhere.
+        winners
+            .into_iter()
+            .map(|b| Win {
+                board: b,
+                draw,
+                draw_index,
+                score: self.boards[b].unmarked(&self.marked[b]) * draw as u64,
+            })
+            .collect()
+    }
+
+    pub fn finished(&self) -> bool {
+        self.won.iter().all(|&won| won)
+    }
+
+    // Every board that wins, in the order they win.  The boards that never
+    // do aren't in it.
+    pub fn play(boards: &[BingoBoard], draw_order: &[u8], rules: Rules) -> Vec<Win> {
+        let mut game = BingoGame::new(boards, rules);
+        let mut wins = vec![];
+
+        for &draw in draw_order {
+            if game.finished() {
+                break;
+            }
+            wins.extend(game.draw(draw));
+        }
+
+        wins
+    }
+}
+
+// How good a bet a board is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Odds {
+    pub board: usize,
+    // with the draws in the order given, if it wins at all
+    pub draws_to_win: Option<usize>,
+    // how often it wins first with the same numbers drawn in a random order
+    pub chance: f64,
+    pub can_win: bool,
+}
+
+// Which board to play: how each one does with the draw order as given, and
+// with the same numbers drawn in `trials` random orders.  Boards that win
+// together share the win.
+pub fn analyse(
+    boards: &[BingoBoard],
+    draw_order: &[u8],
+    rules: Rules,
+    rng: &mut Rng,
+    trials: usize,
+) -> Vec<Odds> {
+    let mut draws_to_win = vec![None; boards.len()];
+    for win in BingoGame::play(boards, draw_order, rules) {
+        draws_to_win[win.board] = Some(win.draw_index + 1);
+    }
+
+    let mut firsts = vec![0.0; boards.len()];
+    let mut order = draw_order.to_vec();
+    for _ in 0..trials {
+        rng.shuffle(&mut order);
+        let mut game = BingoGame::new(boards, rules);
+        if let Some(wins) = order.iter().map(|&x| game.draw(x)).find(|w| !w.is_empty()) {
+            for win in &wins {
+                firsts[win.board] += 1.0 / wins.len() as f64;
+            }
+        }
+    }
+
+    boards
+        .iter()
+        .enumerate()
+        .map(|(b, board)| Odds {
+            board: b,
+            draws_to_win: draws_to_win[b],
+            chance: firsts[b] / trials.max(1) as f64,
+            can_win: board.can_win(draw_order, rules),
+        })
+        .collect()
+}
+
+// `lines` starts with the blank line after the draw order, which is line 2.
+fn parse(lines: &[String]) -> Result<Vec<BingoBoard>, Box<dyn std::error::Error>> {
+    let mut bingo_boards: Vec<BingoBoard> = vec![];
+
+    let mut rows: Vec<Vec<u8>> = vec![];
+    let finish =
+        |rows: Vec<Vec<u8>>, n: usize| BingoBoard::new(rows).map_err(|e| Located::new(n, e));
+
+    for (n, board_line) in lines.iter().enumerate().skip(1) {
+        if board_line.is_empty() {
+            bingo_boards.push(finish(rows, n + 1)?);
+            rows = vec![];
+        } else {
+            let row = parse_numbers(board_line, board_line.split_whitespace())
+                .map_err(|e| Located::new(n + 2, e))?;
+            if rows.first().is_some_and(|first| first.len() != row.len()) {
+                let e = BingoError::Row(row.len(), rows[0].len());
+                return Err(Located::new(n + 2, e).into());
+            }
+            rows.push(row);
+        }
+    }
+    // There's no blank line after the last board.
+    if !rows.is_empty() {
+        bingo_boards.push(finish(rows, lines.len() + 1)?);
+    }
+
+    if bingo_boards.is_empty() {
+        return Err(BingoError::NoBoards.into());
+    }
+    Ok(bingo_boards)
+}
+
+const EXAMPLE: &str = "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7";
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: usize = 4;
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("4512"),
+        part2: Some("1924"),
+    }];
+
+    // The draw order, and every board.
+    type Input = (Vec<u8>, Vec<BingoBoard>);
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input, Box<dyn std::error::Error>> {
+        let first_line = match lines.first() {
+            Some(line) if !line.is_empty() => line,
+            _ => return Err(BingoError::Empty.into()),
+        };
+
+        let draw_order =
+            parse_numbers(first_line, first_line.split(',')).map_err(|e| Located::new(1, e))?;
+
+        let boards = parse(&lines[1..])?;
+
+        Ok((draw_order, boards))
+    }
+
+    fn part1(
+        (draw_order, boards): &Self::Input,
+    ) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+        let wins = BingoGame::play(boards, draw_order, Rules::default());
+        let first = wins.first().ok_or(BingoError::NoWinner)?;
+
+        common::note("board", first.board + 1);
+        common::note("draw_index", first.draw_index);
+        Ok(first.score)
+    }
+
+    fn part2(
+        (draw_order, boards): &Self::Input,
+    ) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+        let wins = BingoGame::play(boards, draw_order, Rules::default());
+        let last = wins.last().ok_or(BingoError::NoWinner)?;
+
+        common::note("board", last.board + 1);
+        common::note("draw_index", last.draw_index);
+        common::note("never_win", boards.len() - wins.len());
+        Ok(last.score)
+    }
+
+    // Every number from 0 to 99 drawn in some order, so every board wins
+    // eventually, and `size` boards.
+    fn generate(rng: &mut Rng, size: Option<usize>) -> Vec<String> {
+        let mut numbers: Vec<usize> = (0..100).collect();
+        rng.shuffle(&mut numbers);
+        let draws: Vec<String> = numbers.iter().map(|n| n.to_string()).collect();
+        let mut lines = vec![draws.join(",")];
+
+        for _ in 0..size.unwrap_or(100) {
+            rng.shuffle(&mut numbers);
+            lines.push(String::new());
+            for row in numbers[..25].chunks(5) {
+                let row: Vec<String> = row.iter().map(|n| format!("{:2}", n)).collect();
+                lines.push(row.join(" "));
+            }
+        }
+
+        lines
+    }
+}
+
+pub fn run(source: Option<Source>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    common::run::<Day4>(source)
+}
+
+common::solution_tests!(Day4);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn board(rows: &[&[u8]]) -> BingoBoard {
+        BingoBoard::new(rows.iter().map(|row| row.to_vec()).collect()).unwrap()
+    }
+
+    #[test]
+    fn ranking_test() {
+        let (draw_order, boards) =
+            Day4::parse(EXAMPLE.lines().map(String::from).collect()).unwrap();
+        let wins = BingoGame::play(&boards, &draw_order, Rules::default());
+
+        let order: Vec<(usize, u8, usize)> = wins
+            .iter()
+            .map(|w| (w.board, w.draw, w.draw_index))
+            .collect();
+        assert!(order == vec![(2, 24, 11), (0, 16, 13), (1, 13, 14)]);
+        assert!(wins[0].score == 4512 && wins[2].score == 1924);
+    }
+
+    #[test]
+    fn rules_test() {
+        let boards = [
+            board(&[&[1, 2, 3], &[4, 5, 6], &[7, 8, 9]]),
+            board(&[&[9, 1], &[2, 3]]),
+        ];
+        let draws = [3, 5, 7, 9, 1];
+
+        // 3, 5, 7 is the other diagonal, which only counts with diagonals on.
+        let wins = BingoGame::play(&boards, &draws, Rules { diagonals: true });
+        assert!(
+            wins.iter()
+                .map(|w| (w.board, w.draw_index))
+                .collect::<Vec<_>>()
+                == vec![(0, 2), (1, 3)]
+        );
+        assert!(wins[0].score == (1 + 2 + 4 + 6 + 8 + 9) * 7);
+
+        let wins = BingoGame::play(&boards, &draws, Rules::default());
+        assert!(wins.len() == 1 && wins[0].board == 1);
+        assert!(wins[0].score == 2);
+    }
+
+    #[test]
+    fn shape_test() {
+        assert!(BingoBoard::new(vec![vec![1, 2], vec![3]]) == Err(BingoError::Row(1, 2)));
+        assert!(BingoBoard::new(vec![vec![1, 2]]) == Err(BingoError::Board(1, 2)));
+        assert!(BingoBoard::new(vec![]) == Err(BingoError::Board(0, 0)));
+
+        let lines: Vec<String> = ["1", "", "1 2", "3"]
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        let e = Day4::parse(lines).unwrap_err().to_string();
+        assert!(e == "line 4: row has 1 numbers, but the board's first row has 2");
+    }
+
+    #[test]
+    fn analyse_test() {
+        let (draw_order, boards) =
+            Day4::parse(EXAMPLE.lines().map(String::from).collect()).unwrap();
+        let odds = analyse(
+            &boards,
+            &draw_order,
+            Rules::default(),
+            &mut Rng::new(4),
+            200,
+        );
+
+        let draws: Vec<Option<usize>> = odds.iter().map(|o| o.draws_to_win).collect();
+        assert!(draws == vec![Some(14), Some(15), Some(12)]);
+        assert!(odds.iter().all(|o| o.can_win && o.chance > 0.0));
+        assert!((odds.iter().map(|o| o.chance).sum::<f64>() - 1.0).abs() < 1e-9);
+        // The same seed, the same answer.
+        assert!(
+            odds == analyse(
+                &boards,
+                &draw_order,
+                Rules::default(),
+                &mut Rng::new(4),
+                200
+            )
+        );
+
+        // 2 and 3 only make a line if diagonals count.
+        let board = board(&[&[1, 2], &[3, 4]]);
+        assert!(board.can_win(&[2, 3], Rules { diagonals: true }));
+        assert!(!board.can_win(&[2, 3], Rules::default()));
+        let odds = analyse(&[board], &[3, 2], Rules::default(), &mut Rng::new(1), 10);
+        assert!(!odds[0].can_win && odds[0].chance == 0.0 && odds[0].draws_to_win.is_none());
+    }
+}
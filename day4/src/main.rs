@@ -1,101 +1,117 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader, Lines};
-use std::path::PathBuf;
-
-fn parse(lines: &mut Lines<BufReader<File>>) -> Result<Vec<Vec<u8>>, Box<dyn std::error::Error>> {
-    let mut bingo_boards: Vec<Vec<u8>> = vec![];
-
-    let mut board: Vec<u8> = vec![];
-
-    for board_lines in lines.skip(1) {
-        let board_lines = board_lines?;
-        if board_lines.is_empty() {
-            bingo_boards.push(board);
-            board = vec![];
-        } else {
-            board_lines
-                .split_whitespace()
-                .for_each(|x| board.push(x.parse::<u8>().unwrap()));
-        }
-    }
-    Ok(bingo_boards)
+use common::{table, Args, Rng, Solution, Subcommand};
+use day4::{BingoBoard, BingoGame, Day4, Rules};
+use std::num::NonZeroUsize;
+
+const USAGE: &str = "usage: day4 [<path>]
+       day4 wins [--diagonals] [<path>]
+       day4 analyse [--diagonals] [--seed <n>] [--trials <n>] [<path>]
+
+<path> is the puzzle input, or - for stdin
+
+wins         list every board in the order it wins, with the number that won
+             it and its score
+analyse      how many draws each board takes to win, how often it would win
+             first if the numbers came out in a random order, and whether it
+             can win at all
+--diagonals  count the diagonals as lines too
+--seed       shuffle the draws the same way as a previous run (default random)
+--trials     how many random orders to try (default 1000)";
+
+const COMMANDS: &[Subcommand] = &[
+    Subcommand {
+        name: "wins",
+        switches: &["--diagonals"],
+        values: &[],
+        arguments: &[],
+    },
+    Subcommand {
+        name: "analyse",
+        switches: &["--diagonals"],
+        values: &["--seed", "--trials"],
+        arguments: &[],
+    },
+];
+
+const DEFAULT_TRIALS: NonZeroUsize = NonZeroUsize::new(1000).unwrap();
+
+type Input = (Vec<u8>, Vec<BingoBoard>);
+
+fn load(args: &Args) -> Result<Input, Box<dyn std::error::Error>> {
+    Day4::parse(common::load::<Day4>(args.source())?)
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mut path = PathBuf::new();
-    path.push(std::env::var("CARGO_MANIFEST_DIR")?);
-    path.push("input");
+fn rules(args: &Args) -> Rules {
+    Rules {
+        diagonals: args.switch("--diagonals"),
+    }
+}
 
-    let input = File::open(path)?;
-    let reader = BufReader::new(input);
-    let mut lines = reader.lines();
-    let first_line = lines.next().unwrap()?;
+fn wins(args: &Args) -> Result<(), Box<dyn std::error::Error>> {
+    let (draw_order, boards) = load(args)?;
+    let wins = BingoGame::play(&boards, &draw_order, rules(args));
 
-    let draw_order: Vec<u8> = first_line
-        .split(',')
-        .map(|n| n.parse::<u8>().unwrap())
+    let rows: Vec<Vec<String>> = wins
+        .iter()
+        .enumerate()
+        .map(|(n, win)| {
+            vec![
+                (n + 1).to_string(),
+                (win.board + 1).to_string(),
+                win.draw.to_string(),
+                (win.draw_index + 1).to_string(),
+                win.score.to_string(),
+            ]
+        })
         .collect();
+    table::print(&["Rank", "Board", "Number", "Draw", "Score"], &rows);
 
-    let mut boards = parse(&mut lines)?;
-    let board_count = boards.len();
-    let mut matches_list: Vec<[bool; 25]> = vec![[false; 25]; boards.len()];
-    assert!(board_count > 0);
-
-    for draw in draw_order {
-        let mut new_winners: Vec<usize> = vec![];
-        for (j, board) in boards.iter().enumerate() {
-            for (i, x) in board.iter().enumerate() {
-                if x == &draw {
-                    let mut matches = matches_list.get(j).unwrap().clone();
-                    matches[i] = true;
-                    matches_list.push(matches);
-                    matches_list.swap_remove(j);
-
-                    let row = i - (i % 5);
-
-                    if !((matches[(i + 5) % 25]
-                        && matches[(i + 10) % 25]
-                        && matches[(i + 15) % 25]
-                        && matches[(i + 20) % 25])
-                        || (matches[row]
-                            && matches[row + 1]
-                            && matches[row + 2]
-                            && matches[row + 3]
-                            && matches[row + 4]))
-                    {
-                        continue;
-                    }
-
-                    let winner_count = board_count - boards.len() + new_winners.len();
-                    if winner_count == 0 || winner_count == board_count - 1 {
-                        let unmarked_sum: u64 = board
-                            .iter()
-                            .enumerate()
-                            .filter(|&(i, _)| !matches[i])
-                            .fold(0, |acc, (_, x)| acc + (*x as u64));
-
-                        let total = unmarked_sum * (draw as u64);
-                        if winner_count == 0 {
-                            println!("Part 1: {}", total);
-                        } else {
-                            println!("Part 2: {}", total);
-                        }
-                    }
-                    new_winners.push(j);
-                    break;
-                }
-            }
-        }
-
-        // We're going to remove entries from the vec, so we need to remove from the right
-        // to preserve existing indices.
-        new_winners.sort();
-        new_winners.reverse();
-        for x in new_winners {
-            boards.remove(x);
-            matches_list.remove(x);
-        }
+    let mut losers: Vec<usize> = (1..=boards.len()).collect();
+    losers.retain(|b| wins.iter().all(|win| win.board + 1 != *b));
+    if !losers.is_empty() {
+        let losers: Vec<String> = losers.iter().map(|b| b.to_string()).collect();
+        println!("\nNever won: {}", losers.join(", "));
     }
+    Ok(())
+}
+
+fn analyse(args: &Args) -> Result<(), Box<dyn std::error::Error>> {
+    let trials = args.value("--trials")?.unwrap_or(DEFAULT_TRIALS);
+    let (draw_order, boards) = load(args)?;
+
+    let seed = args.value("--seed")?.unwrap_or_else(Rng::time_seed);
+    // On stderr, so the table can go somewhere without it.
+    eprintln!("seed {}", seed);
+    let mut rng = Rng::new(seed);
+    let odds = day4::analyse(&boards, &draw_order, rules(args), &mut rng, trials.get());
+
+    let rows: Vec<Vec<String>> = odds
+        .iter()
+        .map(|o| {
+            vec![
+                (o.board + 1).to_string(),
+                o.draws_to_win.map_or(String::from("-"), |n| n.to_string()),
+                format!("{:.1}%", o.chance * 100.0),
+                String::from(if o.can_win { "yes" } else { "no" }),
+            ]
+        })
+        .collect();
+    table::print(&["Board", "Draws to win", "Wins first", "Can win"], &rows);
 
+    let best = odds.iter().max_by(|a, b| a.chance.total_cmp(&b.chance));
+    if let Some(best) = best.filter(|best| best.chance > 0.0) {
+        println!("\nBest bet: board {}", best.board + 1);
+    }
     Ok(())
 }
+
+fn main() {
+    let args = common::args::parse::<Day4>(COMMANDS, USAGE);
+
+    let result = match args.command() {
+        Some("wins") => wins(&args),
+        Some("analyse") => analyse(&args),
+        _ => common::answer::<Day4>(args.path()),
+    };
+
+    common::exit_on_error::<Day4>(result);
+}
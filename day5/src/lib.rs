@@ -0,0 +1,312 @@
+use common::{Example, Grid, Located, Reference, Rng, Solution, Source};
+
+#[derive(Debug)]
+struct Point {
+    x: u16,
+    y: u16,
+}
+
+#[derive(Debug)]
+enum Orientation {
+    Vertical,
+    Horizontal,
+    Diagonal,
+}
+
+#[derive(Debug)]
+pub struct Line {
+    start: Point,
+    end: Point,
+    orientation: Orientation,
+}
+
+impl Line {
+    // Every point the line covers, from start to end.
+    fn points(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let dx = (self.end.x as i32 - self.start.x as i32).signum();
+        let dy = (self.end.y as i32 - self.start.y as i32).signum();
+        let length = self
+            .start
+            .x
+            .abs_diff(self.end.x)
+            .max(self.start.y.abs_diff(self.end.y)) as i32;
+
+        (0..=length).map(move |n| {
+            (
+                (self.start.x as i32 + dx * n) as usize,
+                (self.start.y as i32 + dy * n) as usize,
+            )
+        })
+    }
+}
+
+// The floor draw_grid works on is this many points each way.
+const FLOOR_SIZE: u16 = 999;
+
+#[derive(Debug, PartialEq)]
+pub enum VentParseError {
+    Empty,
+    Shape,
+    // column, and what was there
+    Coordinate(usize, String),
+    Angle,
+}
+
+impl std::error::Error for VentParseError {}
+
+impl std::fmt::Display for VentParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            VentParseError::Empty => write!(f, "no vents to map"),
+            VentParseError::Shape => write!(f, "expected \"x1,y1 -> x2,y2\""),
+            VentParseError::Coordinate(column, s) => write!(
+                f,
+                "\"{}\" at column {} isn't a coordinate below {}",
+                s, column, FLOOR_SIZE
+            ),
+            VentParseError::Angle => write!(f, "vent isn't straight or at 45 degrees"),
+        }
+    }
+}
+
+fn parse_point(line: &str, point: &str) -> Result<Point, VentParseError> {
+    let (x, y) = point.split_once(',').ok_or(VentParseError::Shape)?;
+    let coordinate = |s: &str| match s.parse::<u16>() {
+        Ok(n) if n < FLOOR_SIZE => Ok(n),
+        _ => Err(VentParseError::Coordinate(
+            common::parse::column(line, s),
+            s.to_string(),
+        )),
+    };
+
+    Ok(Point {
+        x: coordinate(x)?,
+        y: coordinate(y)?,
+    })
+}
+
+fn parse(lines: Vec<String>) -> Result<Vec<Line>, Box<dyn std::error::Error>> {
+    let mut pairs: Vec<Line> = vec![];
+
+    // Can you tell my parsing rigidity is getting lazier as we go? lol
+    for (n, line) in lines.iter().enumerate() {
+        let located = |e| Located::new(n + 1, e);
+        let (start, end) = line
+            .split_once(" -> ")
+            .ok_or(VentParseError::Shape)
+            .map_err(located)?;
+        let start = parse_point(line, start).map_err(located)?;
+        let end = parse_point(line, end).map_err(located)?;
+
+        if start.x != end.x
+            && start.y != end.y
+            && start.x.abs_diff(end.x) != start.y.abs_diff(end.y)
+        {
+            return Err(located(VentParseError::Angle).into());
+        }
+
+        // line.start should be lower than line.end; for diag, we make the lower x value the start
+        let (lower, higher, orientation) = if start.y == end.y {
+            let (lower, higher) = if start.x < end.x {
+                (start, end)
+            } else {
+                (end, start)
+            };
+            (lower, higher, Orientation::Horizontal)
+        } else if start.x == end.x {
+            let (lower, higher) = if start.y < end.y {
+                (start, end)
+            } else {
+                (end, start)
+            };
+            (lower, higher, Orientation::Vertical)
+        } else {
+            let (lower, higher) = if start.x < end.x {
+                (start, end)
+            } else {
+                (end, start)
+            };
+            (lower, higher, Orientation::Diagonal)
+        };
+
+        let line = Line {
+            start: lower,
+            end: higher,
+            orientation,
+        };
+
+        pairs.push(line);
+    }
+    if pairs.is_empty() {
+        return Err(VentParseError::Empty.into());
+    }
+    Ok(pairs)
+}
+
+fn draw_grid(data: &[Line]) -> Vec<[u16; FLOOR_SIZE as usize]> {
+    // LAZY TO THE MAX
+    // we can definitely be more space efficient than this, but it's Sunday, cmon
+    let mut grid = vec![[0u16; FLOOR_SIZE as usize]; FLOOR_SIZE as usize];
+
+    for line in data {
+        match line.orientation {
+            Orientation::Vertical => {
+                for y in line.start.y..line.end.y + 1 {
+                    grid[y as usize][line.start.x as usize] += 1;
+                }
+            }
+            Orientation::Horizontal => {
+                for x in line.start.x..line.end.x + 1 {
+                    grid[line.start.y as usize][x as usize] += 1;
+                }
+            }
+            Orientation::Diagonal => {
+                // We know our x value will be lower from parsing, so we just need
+                // to figure out the y direction.
+                let y_inc = if line.start.y < line.end.y { 1 } else { -1 };
+                let mut y = line.start.y as i16;
+                for x in line.start.x..line.end.x + 1 {
+                    // Just doing this so we can split parts 1 and 2.
+                    grid[y as usize][x as usize] += 0xff;
+                    y += y_inc;
+                }
+            }
+        }
+    }
+
+    grid
+}
+
+// Only as much floor as the vents cover, and we only need to know when a
+// point gets its second vent, so counting stops there.
+fn count_overlaps(data: &[Line], diagonals: bool) -> usize {
+    let width = data.iter().map(|line| line.start.x.max(line.end.x)).max();
+    let height = data.iter().map(|line| line.start.y.max(line.end.y)).max();
+    let mut floor = Grid::new(
+        width.unwrap_or(0) as usize + 1,
+        height.unwrap_or(0) as usize + 1,
+        0u8,
+    );
+    let mut overlaps = 0;
+
+    for line in data {
+        if !diagonals && matches!(line.orientation, Orientation::Diagonal) {
+            continue;
+        }
+        for point in line.points() {
+            if floor[point] < 2 {
+                floor[point] += 1;
+                if floor[point] == 2 {
+                    overlaps += 1;
+                }
+            }
+        }
+    }
+
+    overlaps
+}
+
+const EXAMPLE: &str = "0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2";
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: usize = 5;
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("5"),
+        part2: Some("12"),
+    }];
+
+    type Input = Vec<Line>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input, Box<dyn std::error::Error>> {
+        parse(lines)
+    }
+
+    fn part1(data: &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+        Ok(count_overlaps(data, false))
+    }
+
+    fn part2(data: &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+        Ok(count_overlaps(data, true))
+    }
+
+    // `size` lines, some of them diagonal.
+    fn generate(rng: &mut Rng, size: Option<usize>) -> Vec<String> {
+        let directions = [
+            (1, 0),
+            (0, 1),
+            (1, 1),
+            (1, -1),
+            (-1, 0),
+            (0, -1),
+            (-1, -1),
+            (-1, 1),
+        ];
+        let edge = FLOOR_SIZE as i64 - 1;
+
+        (0..size.unwrap_or(500))
+            .map(|_| {
+                let (x, y) = (rng.between(0, edge), rng.between(0, edge));
+                let &(dx, dy) = rng.choose(&directions);
+                // As far as it can go that way without falling off the floor.
+                let room = |start: i64, d: i64| match d {
+                    1 => edge - start,
+                    -1 => start,
+                    _ => edge,
+                };
+                let length = rng.between(0, room(x, dx).min(room(y, dy)).min(500));
+
+                format!("{},{} -> {},{}", x, y, x + dx * length, y + dy * length)
+            })
+            .collect()
+    }
+}
+
+// Drawing every vent onto the whole floor and counting afterwards.
+impl Reference for Day5 {
+    fn reference1(data: &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+        let grid = draw_grid(data);
+
+        Ok(grid.iter().flatten().filter(|x| (**x % 0xff) >= 2).count())
+    }
+
+    fn reference2(data: &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+        let grid = draw_grid(data);
+
+        Ok(grid
+            .iter()
+            .flatten()
+            .map(|x| {
+                if *x < 0xff {
+                    *x
+                } else {
+                    // un-"hash" our diagonal matches, and add them to the count
+                    let diag_matches = *x / 0xff;
+                    (*x % 0xff) + diag_matches
+                }
+            })
+            .filter(|x| *x >= 2)
+            .count())
+    }
+}
+
+pub fn run(source: Option<Source>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    common::run::<Day5>(source)
+}
+
+common::solution_tests!(Day5);
+common::reference_tests!(Day5, [1, 2, 3, 5, 10, 50]);
@@ -0,0 +1,114 @@
+use common::{Example, Located, Rng, Solution, Source};
+
+#[derive(Debug, PartialEq)]
+pub enum FishParseError {
+    Empty,
+    // column, and what was there
+    Timer(usize, String),
+}
+
+impl std::error::Error for FishParseError {}
+
+impl std::fmt::Display for FishParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FishParseError::Empty => write!(f, "no lanternfish to count"),
+            FishParseError::Timer(column, s) => {
+                write!(
+                    f,
+                    "\"{}\" at column {} isn't a timer from 0 to 8",
+                    s, column
+                )
+            }
+        }
+    }
+}
+
+fn parse(lines: Vec<String>) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let line = match lines.first() {
+        Some(line) if !line.is_empty() => line,
+        _ => return Err(FishParseError::Empty.into()),
+    };
+
+    let digits = line
+        .split(",")
+        .map(|x| match x.parse::<u8>() {
+            Ok(n) if n <= 8 => Ok(n),
+            _ => Err(FishParseError::Timer(
+                common::parse::column(line, x),
+                x.to_string(),
+            )),
+        })
+        .collect::<Result<Vec<u8>, FishParseError>>()
+        .map_err(|e| Located::new(1, e))?;
+
+    Ok(digits)
+}
+
+pub fn count_fish(starting_fish: &[u8], days: usize) -> u64 {
+    // I spent a lot of time trying to figure out the scaling formula before
+    // concluding there's a reason I didn't become a mathematician.
+    // Let's just do it the fast way instead.
+
+    // 9 days is the maximum cycle time.
+    // All we need to keep track of is when each fish creates new fish.
+    let mut fish = [0_u64; 9];
+
+    for x in starting_fish {
+        fish[*x as usize] += 1;
+    }
+
+    for day in 0..days {
+        // In 7 days, the fish that created offspring today will do so again.
+        // Since `fish` is both tracking the total amount of fish and the fish
+        // spawns per cycle, we can kill two birds with one stone here.
+        // You can think of fish[day % 9] as now containing today's new fish,
+        // and of today's parents as moving to fish[(day + 7) % 9].
+        fish[(day + 7) % 9] += fish[day % 9];
+    }
+
+    fish.iter().sum::<u64>()
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: usize = 6;
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: "3,4,3,1,2",
+        part1: Some("5934"),
+        part2: Some("26984457539"),
+    }];
+
+    type Input = Vec<u8>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input, Box<dyn std::error::Error>> {
+        parse(lines)
+    }
+
+    fn part1(starting_fish: &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+        Ok(count_fish(starting_fish, 80))
+    }
+
+    fn part2(starting_fish: &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+        Ok(count_fish(starting_fish, 256))
+    }
+
+    // `size` fish, all partway through their cycle like the puzzle's.
+    fn generate(rng: &mut Rng, size: Option<usize>) -> Vec<String> {
+        let timers: Vec<String> = (0..size.unwrap_or(300).max(1))
+            .map(|_| rng.between(1, 5).to_string())
+            .collect();
+
+        vec![timers.join(",")]
+    }
+}
+
+pub fn run(source: Option<Source>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    common::run::<Day6>(source)
+}
+
+common::solution_tests!(Day6);
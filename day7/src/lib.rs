@@ -0,0 +1,150 @@
+use common::{Example, Located, Reference, Rng, Solution, Source};
+
+#[derive(Debug, PartialEq)]
+pub enum CrabParseError {
+    Empty,
+    // column, and what was there
+    Position(usize, String),
+}
+
+impl std::error::Error for CrabParseError {}
+
+impl std::fmt::Display for CrabParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CrabParseError::Empty => write!(f, "no crabs to line up"),
+            CrabParseError::Position(column, s) => {
+                write!(f, "\"{}\" at column {} isn't a position", s, column)
+            }
+        }
+    }
+}
+
+fn parse(lines: Vec<String>) -> Result<Vec<u16>, Box<dyn std::error::Error>> {
+    let line = match lines.first() {
+        Some(line) if !line.is_empty() => line,
+        _ => return Err(CrabParseError::Empty.into()),
+    };
+
+    let digits = line
+        .split(",")
+        .map(|x| match x.parse::<u16>() {
+            Ok(n) => Ok(n),
+            _ => Err(CrabParseError::Position(
+                common::parse::column(line, x),
+                x.to_string(),
+            )),
+        })
+        .collect::<Result<Vec<u16>, CrabParseError>>()
+        .map_err(|e| Located::new(1, e))?;
+
+    Ok(digits)
+}
+
+fn distance(from: u16, to: u16) -> u64 {
+    from.abs_diff(to) as u64
+}
+
+// What it costs to move now that each step costs one more than the last.
+fn triangle(from: u16, to: u16) -> u64 {
+    let distance = distance(from, to);
+
+    (distance * (distance + 1)) / 2
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: usize = 7;
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: "16,1,2,0,4,2,7,1,2,14",
+        part1: Some("37"),
+        part2: Some("168"),
+    }];
+
+    type Input = Vec<u16>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input, Box<dyn std::error::Error>> {
+        let mut crabs = parse(lines)?;
+
+        crabs.sort();
+
+        Ok(crabs)
+    }
+
+    // Moving to the median is never worse than moving anywhere else: every
+    // step away from it takes more crabs further than it brings closer.
+    fn part1(crabs: &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+        let median = crabs[crabs.len() / 2];
+
+        Ok(crabs.iter().map(|&x| distance(x, median)).sum())
+    }
+
+    // With triangular costs the best place is within half a step of the mean,
+    // and rounding could take us a step from that, so try a step either side.
+    fn part2(crabs: &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+        let mean = crabs.iter().map(|&x| x as usize).sum::<usize>() / crabs.len();
+
+        Ok((mean.saturating_sub(1)..=mean + 1)
+            .map(|to| crabs.iter().map(|&x| triangle(x, to as u16)).sum())
+            .min()
+            .unwrap())
+    }
+
+    // `size` crabs, spread over twice as many positions.
+    fn generate(rng: &mut Rng, size: Option<usize>) -> Vec<String> {
+        let crabs = size.unwrap_or(1000).max(1);
+        let positions: Vec<String> = (0..crabs)
+            .map(|_| rng.below(crabs * 2).to_string())
+            .collect();
+
+        vec![positions.join(",")]
+    }
+}
+
+// The long way round: try every position and keep the cheapest.
+impl Reference for Day7 {
+    fn reference1(crabs: &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+        Ok(crabs.iter().fold(u64::MAX, |best, x| {
+            let distance: u64 = crabs
+                .iter()
+                .map(|y| (*x as i32 - *y as i32).unsigned_abs() as u64)
+                .sum();
+            if distance < best {
+                distance
+            } else {
+                best
+            }
+        }))
+    }
+
+    fn reference2(crabs: &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+        Ok(
+            (0..=*crabs.iter().last().unwrap()).fold(u64::MAX, |best, x| {
+                let fuel: u64 = crabs
+                    .iter()
+                    .map(|y| {
+                        let distance = (x as i32 - *y as i32).unsigned_abs() as u64;
+                        // yes I googled the formula for triangular numbers.
+                        (distance * (distance + 1)) / 2
+                    })
+                    .sum();
+                if fuel < best {
+                    fuel
+                } else {
+                    best
+                }
+            }),
+        )
+    }
+}
+
+pub fn run(source: Option<Source>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    common::run::<Day7>(source)
+}
+
+common::solution_tests!(Day7);
+common::reference_tests!(Day7, [1, 2, 3, 5, 10, 100]);
@@ -0,0 +1,338 @@
+use common::{Example, Located, Rng, Solution, Source};
+
+#[derive(Debug, PartialEq)]
+pub enum EntryError {
+    Empty,
+    Separator,
+    // column, and what was there
+    Segment(usize, char),
+    Patterns(usize),
+    Digits(usize),
+    Wiring,
+}
+
+impl std::error::Error for EntryError {}
+
+impl std::fmt::Display for EntryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            EntryError::Empty => write!(f, "no entries to decode"),
+            EntryError::Separator => write!(f, "expected \"<patterns> | <output>\""),
+            EntryError::Segment(column, c) => {
+                write!(
+                    f,
+                    "\"{}\" at column {} isn't a segment from a to g",
+                    c, column
+                )
+            }
+            EntryError::Patterns(n) => write!(f, "{} signal patterns, expected 10", n),
+            EntryError::Digits(n) => write!(f, "{} output digits, expected 4", n),
+            EntryError::Wiring => write!(f, "no wiring fits these signal patterns"),
+        }
+    }
+}
+
+fn char_to_digit(ch: char) -> Option<u8> {
+    match ch {
+        'a' => Some(0),
+        'b' => Some(1),
+        'c' => Some(2),
+        'd' => Some(3),
+        'e' => Some(4),
+        'f' => Some(5),
+        'g' => Some(6),
+        _ => None,
+    }
+}
+
+fn count_digits(digits: Vec<u8>) -> u8 {
+    digits.iter().filter(|&x| *x == 1).count() as u8
+}
+
+// The ten unique signal patterns, and the four output digits.
+pub type Entry = (Vec<Vec<u8>>, Vec<Vec<u8>>);
+
+// Turn each word into the segments it lights up.
+fn parse_segments(line: &str, words: &str) -> Result<Vec<Vec<u8>>, EntryError> {
+    words
+        .split_whitespace()
+        .map(|x| {
+            let mut segments = [0u8; 7];
+            for (n, ch) in x.chars().enumerate() {
+                let column = common::parse::column(line, x) + n;
+                let segment = char_to_digit(ch).ok_or(EntryError::Segment(column, ch))?;
+                segments[segment as usize] = 1;
+            }
+            Ok(segments.to_vec())
+        })
+        .collect()
+}
+
+fn parse_entry(line: &str) -> Result<Entry, EntryError> {
+    let (patterns, output) = line.split_once("|").ok_or(EntryError::Separator)?;
+
+    let patterns = parse_segments(line, patterns)?;
+    if patterns.len() != 10 {
+        return Err(EntryError::Patterns(patterns.len()));
+    }
+    let output = parse_segments(line, output)?;
+    if output.len() != 4 {
+        return Err(EntryError::Digits(output.len()));
+    }
+
+    Ok((patterns, output))
+}
+
+fn parse(lines: Vec<String>) -> Result<Vec<Entry>, Box<dyn std::error::Error>> {
+    let mut entries = vec![];
+
+    for (n, line) in lines.iter().enumerate() {
+        entries.push(parse_entry(line).map_err(|e| Located::new(n + 1, e))?);
+    }
+
+    if entries.is_empty() {
+        return Err(EntryError::Empty.into());
+    }
+    Ok(entries)
+}
+
+const SEGMENT_LENGTHS: [u8; 10] = [6, 2, 5, 5, 4, 5, 6, 3, 7, 6];
+const SEGMENTS: [[u8; 7]; 10] = [
+    [1, 1, 1, 0, 1, 1, 1], // 0
+    [0, 0, 1, 0, 0, 1, 0], // 1
+    [1, 0, 1, 1, 1, 0, 1], // 2
+    [1, 0, 1, 1, 0, 1, 1], // 3
+    [0, 1, 1, 1, 0, 1, 0], // 4
+    [1, 1, 0, 1, 0, 1, 1], // 5
+    [1, 1, 0, 1, 1, 1, 1], // 6
+    [1, 0, 1, 0, 0, 1, 0], // 7
+    [1, 1, 1, 1, 1, 1, 1], // 8
+    [1, 1, 1, 1, 0, 1, 1], // 9
+];
+
+// Gives up with None if the patterns don't come from a real display.
+fn deduce_segments(pattern: Vec<Vec<u8>>) -> Option<Vec<u8>> {
+    let mut digits = vec![vec![0; 7]; 10];
+    // a == 0, b == 1 etc
+    let mut segments = [255u8; 7];
+
+    let mut c_or_f = vec![];
+    let mut b_or_d = vec![];
+
+    // Solve the easy ones first.
+    [1, 4, 7, 8].iter().for_each(|x| {
+        digits[*x as usize] = pattern
+            .iter()
+            .find(|y| {
+                y.iter().filter(|&z| *z == 1).count() == SEGMENT_LENGTHS[*x as usize] as usize
+            })
+            .cloned()
+            .unwrap_or_default()
+    });
+    if [1, 4, 7, 8].iter().any(|&x| digits[x].is_empty()) {
+        return None;
+    }
+
+    (0..7).for_each(|x| {
+        if digits[1][x as usize] == 1 {
+            c_or_f.push(x);
+        }
+    });
+
+    // Now let's figure out what some of the segments are.
+    // 7 is just 1 with segment 'a' as an extra.
+    segments[0] = (0..7).find(|&x| (digits[7][x] ^ digits[1][x]) == 1)? as u8;
+    // 4 has 'b' and 'd' over 1.
+    (0..7).for_each(|x| {
+        if (digits[4][x] ^ digits[1][x]) == 1 {
+            b_or_d.push(x as u8)
+        }
+    });
+    if c_or_f.len() != 2 || b_or_d.len() != 2 {
+        return None;
+    }
+
+    // Of the three with 6 segments (0, 6, 9), 0 misses 'd', 6 misses 'c', 9 misses 'e'.
+    // So the digit containing all of (b, c, d, f) must be 9.
+    digits[9] = pattern
+        .iter()
+        .filter(|&x| count_digits(x.to_vec()) == 6)
+        .find(|x| {
+            (x[c_or_f[0] as usize]
+                & x[c_or_f[1] as usize]
+                & x[b_or_d[0] as usize]
+                & x[b_or_d[1] as usize])
+                == 1
+        })?
+        .to_vec();
+    // Since we have 9, we can find 'e'.
+    segments[4] = (0..7).find(|&x| digits[9][x as usize] == 0)?;
+
+    // The digit missing one of (b, d) must be 0
+    digits[0] = pattern
+        .iter()
+        .filter(|&x| count_digits(x.to_vec()) == 6)
+        .find(|x| (x[b_or_d[0] as usize] ^ x[b_or_d[1] as usize]) == 1)?
+        .to_vec();
+    // Now we can figure out 'b' and 'd'
+    segments[3] = (0..7).find(|&x| digits[0][x as usize] == 0)?;
+    segments[1] = if b_or_d[0] == segments[3] {
+        b_or_d[1]
+    } else {
+        b_or_d[0]
+    };
+
+    // The digit missing one of (c, f) must be 6
+    digits[6] = pattern
+        .iter()
+        .filter(|&x| count_digits(x.to_vec()) == 6)
+        .find(|x| (x[c_or_f[0] as usize] ^ x[c_or_f[1] as usize]) == 1)?
+        .to_vec();
+    // Now we can figure out 'c' and 'f'
+    segments[2] = (0..7).find(|&x| digits[6][x as usize] == 0)?;
+    segments[5] = if c_or_f[0] == segments[2] {
+        c_or_f[1]
+    } else {
+        c_or_f[0]
+    };
+
+    // We have everything except 'g' now, so it's the odd one out.
+    segments[6] = (0..7).find(|x| !segments.contains(x))?;
+
+    Some(segments.to_vec())
+}
+
+fn translate_output(output: Vec<Vec<u8>>, mapping: Vec<u8>) -> Option<Vec<u8>> {
+    let translated_segments: Vec<Vec<u8>> = SEGMENTS
+        .map(|x| {
+            let mut translation = [0u8; 7];
+            (0..7).for_each(|n| {
+                if x[n] == 1 {
+                    translation[mapping[n] as usize] = 1
+                }
+            });
+            translation.to_vec()
+        })
+        .to_vec();
+
+    output
+        .iter()
+        .map(|num| {
+            translated_segments
+                .iter()
+                .enumerate()
+                .find(|(_, x)| *x == num)
+                .map(|(n, _)| n as u8)
+        })
+        .collect()
+}
+
+fn digits_to_value(digits: Vec<u8>) -> u16 {
+    // Could just do digits[0]*1000 + digits[1]*100 etc but nah
+    digits.iter().fold(0, |value, &x| value * 10 + x as u16)
+}
+
+// Work out the wiring for every entry, and read off its output digits.  The
+// error says which entry (counting from 1) couldn't be wired up.
+pub fn decode(data: &[Entry]) -> Result<Vec<Vec<u8>>, Located<EntryError>> {
+    data.iter()
+        .enumerate()
+        .map(|(n, (pattern, output))| {
+            deduce_segments(pattern.to_vec())
+                .and_then(|mapping| translate_output(output.to_vec(), mapping))
+                .ok_or(Located::new(n + 1, EntryError::Wiring))
+        })
+        .collect()
+}
+
+const EXAMPLE: &str =
+    "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
+dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce";
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: usize = 8;
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf",
+            part1: None,
+            part2: Some("5353"),
+        },
+        Example {
+            input: EXAMPLE,
+            part1: Some("26"),
+            part2: Some("61229"),
+        },
+    ];
+
+    type Input = Vec<Entry>;
+    type Part1 = usize;
+    type Part2 = u64;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input, Box<dyn std::error::Error>> {
+        parse(lines)
+    }
+
+    fn part1(data: &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+        Ok(decode(data)?
+            .iter()
+            .map(|x| {
+                x.iter()
+                    .filter(|&y| *y == 1 || *y == 4 || *y == 7 || *y == 8)
+                    .count()
+            })
+            .sum::<usize>())
+    }
+
+    fn part2(data: &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+        Ok(decode(data)?
+            .iter()
+            .map(|x| digits_to_value(x.to_vec()) as u64)
+            .sum::<u64>())
+    }
+
+    // `size` displays, each wired up differently.
+    fn generate(rng: &mut Rng, size: Option<usize>) -> Vec<String> {
+        const DIGITS: [&str; 10] = [
+            "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+        ];
+
+        (0..size.unwrap_or(200))
+            .map(|_| {
+                let mut wiring: Vec<char> = "abcdefg".chars().collect();
+                rng.shuffle(&mut wiring);
+                let mut digits: Vec<usize> = (0..10).collect();
+                rng.shuffle(&mut digits);
+                let mut pattern = |digit: usize| {
+                    let mut segments: Vec<char> = DIGITS[digit]
+                        .chars()
+                        .map(|c| wiring[c as usize - 'a' as usize])
+                        .collect();
+                    rng.shuffle(&mut segments);
+                    segments.into_iter().collect::<String>()
+                };
+
+                let patterns: Vec<String> = digits.iter().map(|&d| pattern(d)).collect();
+                let output: Vec<String> = digits[..4].iter().map(|&d| pattern(d)).collect();
+
+                format!("{} | {}", patterns.join(" "), output.join(" "))
+            })
+            .collect()
+    }
+}
+
+pub fn run(source: Option<Source>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    common::run::<Day8>(source)
+}
+
+common::solution_tests!(Day8);
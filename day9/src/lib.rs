@@ -0,0 +1,342 @@
+use common::image::Colour;
+use common::{Example, Grid, Rng, Simulation, Solution, Source, Visualise};
+use std::collections::{BTreeMap, VecDeque};
+
+fn parse(lines: Vec<String>) -> Result<Grid<u8>, Box<dyn std::error::Error>> {
+    let digits = Grid::parse(&lines, |c| c.to_digit(10).map(|d| d as u8))?;
+
+    Ok(digits)
+}
+
+// For a given position, this struct tracks what's adjacent to it,
+// and if we've tried going there before.
+// If there's a 9 in a direction, checked_direction = true.
+#[derive(Debug, Copy, Clone)]
+struct Adjacent {
+    up: u8,
+    down: u8,
+    left: u8,
+    right: u8,
+    checked_up: bool,
+    checked_down: bool,
+    checked_left: bool,
+    checked_right: bool,
+}
+
+fn get_adjacent(map: &Grid<u8>, x: usize, y: usize) -> Adjacent {
+    let mut adjacent = Adjacent {
+        up: 9,
+        down: 9,
+        left: 9,
+        right: 9,
+        checked_up: true,
+        checked_down: true,
+        checked_left: true,
+        checked_right: true,
+    };
+
+    if x != 0 {
+        adjacent.left = map[(x - 1, y)];
+        if adjacent.left != 9 {
+            adjacent.checked_left = false;
+        }
+    }
+
+    if x != map.width() - 1 {
+        adjacent.right = map[(x + 1, y)];
+        if adjacent.right != 9 {
+            adjacent.checked_right = false;
+        }
+    }
+
+    if y != 0 {
+        adjacent.up = map[(x, y - 1)];
+        if adjacent.up != 9 {
+            adjacent.checked_up = false;
+        }
+    }
+
+    if y != map.height() - 1 {
+        adjacent.down = map[(x, y + 1)];
+        if adjacent.down != 9 {
+            adjacent.checked_down = false;
+        }
+    }
+
+    adjacent
+}
+
+fn is_smaller_than_adjacent(x: u8, adjacent: Adjacent) -> bool {
+    x < adjacent.up && x < adjacent.down && x < adjacent.left && x < adjacent.right
+}
+
+fn build_basin(map: &Grid<u8>, x_pos: usize, y_pos: usize) -> Vec<(usize, usize)> {
+    // Our starting position is the low point of the basin
+    let mut x = x_pos;
+    let mut y = y_pos;
+    let mut adjacent = get_adjacent(map, x, y);
+    // Maps a (x, y) coordinate to its adjacency data
+    let mut positions: BTreeMap<(usize, usize), Adjacent> = BTreeMap::new();
+
+    positions.insert((x, y), adjacent);
+
+    // This is a very chaotic (read: bad) implementation of a search.
+    // We keep going places until we're in a corner.  Then, we check everywhere
+    // we've been so far for a yet-to-be-explored direction, and jump to that
+    // position.  Once we're out of places to go, we quit.
+    //
+    // I'm definitely not happy with this, but also I've somehow never written
+    // anything like this before, and it's a pretty common thing that comes up
+    // in puzzles and interview questions and stuff.  Definitely gonna check
+    // out how others did it after this.  Maybe I should try some recursion.
+    loop {
+        if !adjacent.checked_up {
+            adjacent.checked_up = true;
+            positions.insert((x, y), adjacent);
+            y -= 1;
+        } else if !adjacent.checked_left {
+            adjacent.checked_left = true;
+            positions.insert((x, y), adjacent);
+            x -= 1;
+        } else if !adjacent.checked_down {
+            adjacent.checked_down = true;
+            positions.insert((x, y), adjacent);
+            y += 1;
+        } else if !adjacent.checked_right {
+            adjacent.checked_right = true;
+            positions.insert((x, y), adjacent);
+            x += 1;
+        } else {
+            // Have we been anywhere that still has some exploration to do?
+            if let Some(unexplored) = positions.iter().find(|&((_, _), adj)| {
+                !adj.checked_down || !adj.checked_up || !adj.checked_left || !adj.checked_right
+            }) {
+                // Jump to that position and keep going
+                x = unexplored.0 .0;
+                y = unexplored.0 .1;
+                adjacent = *unexplored.1;
+                continue;
+            } else {
+                break;
+            }
+        }
+
+        // get_adjacent() would wipe our exploration progress
+        if let std::collections::btree_map::Entry::Vacant(e) = positions.entry((x, y)) {
+            adjacent = get_adjacent(map, x, y);
+            e.insert(adjacent);
+        } else {
+            adjacent = *positions.get(&(x, y)).unwrap()
+        }
+    }
+
+    positions.keys().copied().collect()
+}
+
+// value, x_pos, y_pos
+pub fn find_low_points(heights: &Grid<u8>) -> Vec<(u8, usize, usize)> {
+    heights
+        .positions()
+        .filter(|&(x, y)| is_smaller_than_adjacent(heights[(x, y)], get_adjacent(heights, x, y)))
+        .map(|(x, y)| (heights[(x, y)], x, y))
+        .collect()
+}
+
+// Colours for the basins, going round again when they run out.
+const BASIN_COLOURS: [Colour; 8] = [
+    Colour(230, 25, 75),
+    Colour(60, 180, 75),
+    Colour(255, 225, 25),
+    Colour(0, 130, 200),
+    Colour(245, 130, 48),
+    Colour(145, 30, 180),
+    Colour(70, 240, 240),
+    Colour(240, 50, 230),
+];
+
+// The basins being found one low point at a time.
+pub struct Basins {
+    heights: Grid<u8>,
+    low_points: Vec<(u8, usize, usize)>,
+    // which basin each point is in, once we've found it
+    basins: Grid<Option<usize>>,
+    sizes: Vec<usize>,
+}
+
+impl Simulation for Basins {
+    fn step(&mut self) -> bool {
+        let Some(&(_, x, y)) = self.low_points.get(self.sizes.len()) else {
+            return false;
+        };
+        let basin = build_basin(&self.heights, x, y);
+
+        for &pos in &basin {
+            self.basins[pos] = Some(self.sizes.len());
+        }
+        self.sizes.push(basin.len());
+
+        true
+    }
+
+    fn render(&self) -> String {
+        let mut sizes = self.sizes.clone();
+        sizes.sort();
+        let biggest: Vec<String> = sizes.iter().rev().take(3).map(|n| n.to_string()).collect();
+        let caption = format!(
+            "{} of {} basins found, the biggest {}",
+            self.sizes.len(),
+            self.low_points.len(),
+            biggest.join(", ")
+        );
+
+        let map: Vec<String> = self
+            .basins
+            .rows()
+            .enumerate()
+            .map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(x, basin)| match basin {
+                        Some(n) => (b'a' + (n % 26) as u8) as char,
+                        None if self.heights[(x, y)] == 9 => '#',
+                        None => '.',
+                    })
+                    .collect()
+            })
+            .collect();
+
+        format!("{}\n{}", caption, map.join("\n"))
+    }
+
+    // Each basin in its own colour, and the rest in shades of grey by height.
+    fn picture(&self) -> Option<Grid<Colour>> {
+        let mut picture = self
+            .heights
+            .map(|&h| Colour::WHITE.blend(Colour::BLACK, h as usize, 9));
+
+        for pos in self.basins.positions() {
+            if let Some(n) = self.basins[pos] {
+                picture[pos] = BASIN_COLOURS[n % BASIN_COLOURS.len()];
+            }
+        }
+        Some(picture)
+    }
+}
+
+const EXAMPLE: &str = "2199943210
+3987894921
+9856789892
+8767896789
+9899965678";
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: usize = 9;
+    const MANIFEST_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: EXAMPLE,
+        part1: Some("15"),
+        part2: Some("1134"),
+    }];
+
+    type Input = Grid<u8>;
+    type Part1 = u64;
+    type Part2 = usize;
+
+    fn parse(lines: Vec<String>) -> Result<Self::Input, Box<dyn std::error::Error>> {
+        parse(lines)
+    }
+
+    fn part1(heights: &Self::Input) -> Result<Self::Part1, Box<dyn std::error::Error>> {
+        let low_points = find_low_points(heights);
+
+        Ok(low_points.iter().map(|&x| (x.0 + 1) as u64).sum::<u64>())
+    }
+
+    fn part2(heights: &Self::Input) -> Result<Self::Part2, Box<dyn std::error::Error>> {
+        let mut basin_sizes: Vec<usize> = find_low_points(heights)
+            .iter()
+            .map(|point| build_basin(heights, point.1, point.2).len())
+            .collect();
+
+        // lowest to highest
+        basin_sizes.sort();
+        // highest to lowest
+        basin_sizes.reverse();
+
+        Ok(basin_sizes.iter().take(3).product::<usize>())
+    }
+
+    // A `size` x `size` heightmap shaped like the puzzle's: random walls of
+    // 9s, and everything between them sloping down to a single low point.
+    fn generate(rng: &mut Rng, size: Option<usize>) -> Vec<String> {
+        let size = size.unwrap_or(100).max(1);
+        let mut heights: Grid<Option<u32>> = Grid::new(size, size, None);
+        for height in heights.iter_mut() {
+            if rng.chance(1, 4) {
+                *height = Some(9);
+            }
+        }
+
+        let mut seen = heights.map(|height| height.is_some());
+        for start in heights.positions().collect::<Vec<(usize, usize)>>() {
+            if seen[start] {
+                continue;
+            }
+
+            // Everything we can reach without crossing a wall is one basin.
+            seen[start] = true;
+            let mut basin = vec![start];
+            let mut n = 0;
+            while n < basin.len() {
+                let (x, y) = basin[n];
+                for next in heights.neighbours4(x, y) {
+                    if !seen[next] {
+                        seen[next] = true;
+                        basin.push(next);
+                    }
+                }
+                n += 1;
+            }
+
+            // Then the further from the low point, the higher.
+            let low = *rng.choose(&basin);
+            heights[low] = Some(0);
+            let mut queue = VecDeque::from([low]);
+            while let Some((x, y)) = queue.pop_front() {
+                let height = heights[(x, y)].unwrap_or(0);
+                let next: Vec<(usize, usize)> = heights.neighbours4(x, y).collect();
+                for next in next {
+                    if heights[next].is_none() {
+                        heights[next] = Some((height + 1).min(8));
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+
+        heights
+            .render(|height| char::from_digit(height.unwrap_or(9), 10).unwrap_or('9'))
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+}
+
+impl Visualise for Day9 {
+    fn simulation(heights: &Self::Input) -> Box<dyn Simulation> {
+        Box::new(Basins {
+            heights: heights.clone(),
+            low_points: find_low_points(heights),
+            basins: heights.map(|_| None),
+            sizes: vec![],
+        })
+    }
+}
+
+pub fn run(source: Option<Source>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    common::run::<Day9>(source)
+}
+
+common::solution_tests!(Day9);